@@ -0,0 +1,191 @@
+//! End-to-end throughput benchmarks against scaled mock topologies
+//!
+//! Replays randomly generated mock-substream topologies through the same
+//! pipeline as the Atlas binary (GraphState, TransitiveProcessor,
+//! CanonicalProcessor) and reports events/sec and memory after the replay.
+//!
+//! Run with: cargo bench -p atlas --bench throughput
+
+use atlas::convert::convert_mock_blocks;
+use atlas::events::{SpaceId, SpaceTopologyEvent, SpaceTopologyPayload};
+use atlas::graph::{memory, CanonicalProcessor, GraphState, TransitiveProcessor};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use mock_substream::{MockConfig, MockSubstream};
+use rand::prelude::*;
+
+// ============================================================================
+// Topology generation
+// ============================================================================
+
+/// Generate a random topology with `spaces` spaces as Atlas events
+///
+/// The first created space is used as the root.
+fn generate_topology(spaces: usize, seed: u64) -> (Vec<SpaceTopologyEvent>, SpaceId) {
+    let config = MockConfig::default()
+        .with_num_spaces(spaces)
+        .with_events_per_block(1, 10);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let blocks = MockSubstream::new(config).generate_random_topology(&mut rng);
+    let events = convert_mock_blocks(&blocks);
+
+    let root = events
+        .iter()
+        .find_map(|event| match &event.payload {
+            SpaceTopologyPayload::SpaceCreated(created) => Some(created.space_id),
+            _ => None,
+        })
+        .expect("topology has at least one space");
+
+    (events, root)
+}
+
+// ============================================================================
+// Pipelines
+// ============================================================================
+
+/// Apply events to the graph state and transitive cache only
+fn replay_state(events: &[SpaceTopologyEvent]) -> (GraphState, TransitiveProcessor) {
+    let mut state = GraphState::new();
+    let mut transitive = TransitiveProcessor::new();
+    for event in events {
+        transitive.handle_event(event, &state);
+        state.apply_event(event);
+    }
+    (state, transitive)
+}
+
+/// Full recomputation mode: recompute the canonical graph after every event
+fn replay_full(events: &[SpaceTopologyEvent], root: SpaceId) -> usize {
+    let mut state = GraphState::new();
+    let mut transitive = TransitiveProcessor::new();
+    let mut canonical = CanonicalProcessor::new(root);
+    let mut emitted = 0;
+    for event in events {
+        transitive.handle_event(event, &state);
+        state.apply_event(event);
+        if canonical.compute(&state, &mut transitive).is_some() {
+            emitted += 1;
+        }
+    }
+    emitted
+}
+
+/// Incremental mode: maintain the canonical set with per-event diffs
+fn replay_incremental(events: &[SpaceTopologyEvent], root: SpaceId) -> usize {
+    let mut state = GraphState::new();
+    let mut transitive = TransitiveProcessor::new();
+    let mut canonical = CanonicalProcessor::new(root);
+    let mut changed = 0;
+    for event in events {
+        transitive.handle_event(event, &state);
+        state.apply_event(event);
+        if !canonical.apply_incremental(event, &state).is_empty() {
+            changed += 1;
+        }
+    }
+    changed
+}
+
+// ============================================================================
+// Benchmarks
+// ============================================================================
+
+/// Benchmark event application without canonical computation
+fn bench_state_throughput(c: &mut Criterion) {
+    let mut group = c.benchmark_group("throughput_state");
+    group.sample_size(10);
+
+    for spaces in [1_000, 10_000, 50_000] {
+        let (events, _root) = generate_topology(spaces, 42);
+        group.throughput(Throughput::Elements(events.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(spaces), &events, |b, events| {
+            b.iter(|| black_box(replay_state(events)));
+        });
+    }
+
+    group.finish();
+}
+
+/// Benchmark full recomputation after every event
+fn bench_full_throughput(c: &mut Criterion) {
+    let mut group = c.benchmark_group("throughput_full");
+    group.sample_size(10);
+
+    for spaces in [100, 1_000, 5_000] {
+        let (events, root) = generate_topology(spaces, 42);
+        group.throughput(Throughput::Elements(events.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(spaces), &events, |b, events| {
+            b.iter(|| black_box(replay_full(events, root)));
+        });
+    }
+
+    group.finish();
+}
+
+/// Benchmark incremental canonical maintenance
+fn bench_incremental_throughput(c: &mut Criterion) {
+    let mut group = c.benchmark_group("throughput_incremental");
+    group.sample_size(10);
+
+    for spaces in [1_000, 10_000, 50_000] {
+        let (events, root) = generate_topology(spaces, 42);
+        group.throughput(Throughput::Elements(events.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(spaces), &events, |b, events| {
+            b.iter(|| black_box(replay_incremental(events, root)));
+        });
+    }
+
+    group.finish();
+}
+
+/// Print memory usage after replaying each topology
+///
+/// This is not a timing benchmark - it measures and reports memory usage
+fn bench_replay_memory(c: &mut Criterion) {
+    let mut group = c.benchmark_group("throughput_memory");
+
+    println!("\n");
+    println!("╔══════════════════════════════════════════════════════════════════════════════╗");
+    println!("║                      MEMORY AFTER TOPOLOGY REPLAY                            ║");
+    println!("╠══════════════════════════════════════════════════════════════════════════════╣");
+    println!(
+        "║  {:>8} {:>10} {:>12} {:>14} {:>14}                 ║",
+        "Spaces", "Events", "Edges", "GraphState", "Cache"
+    );
+
+    for spaces in [1_000, 10_000, 50_000] {
+        let (events, root) = generate_topology(spaces, 42);
+        let (state, mut transitive) = replay_state(&events);
+        let _ = CanonicalProcessor::new(root).compute(&state, &mut transitive);
+
+        println!(
+            "║  {:>8} {:>10} {:>12} {:>14} {:>14}                 ║",
+            spaces,
+            events.len(),
+            state.explicit_edge_count() + state.topic_edge_count(),
+            memory::format_bytes(memory::graph_state_size(&state).total_bytes),
+            memory::format_bytes(transitive.cache_memory_bytes())
+        );
+    }
+
+    println!("╚══════════════════════════════════════════════════════════════════════════════╝");
+    println!();
+
+    // Run a trivial benchmark so criterion doesn't complain
+    group.bench_function("memory_measurement_overhead", |b| {
+        let state = GraphState::new();
+        b.iter(|| black_box(memory::graph_state_size(&state)));
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_state_throughput,
+    bench_full_throughput,
+    bench_incremental_throughput,
+    bench_replay_memory,
+);
+
+criterion_main!(benches);
@@ -0,0 +1,55 @@
+//! Feeds a stream of decoded events through the full processing pipeline
+//!
+//! Input is a sequence of records, each a selector byte followed by a
+//! length-delimited Hermes message: even selectors decode `HermesCreateSpace`,
+//! odd selectors decode `HermesSpaceTrustExtension`. Decoding stops at the
+//! first malformed record.
+
+#![no_main]
+
+use atlas::decode::DecodeError;
+use atlas::events::{SpaceTopologyEvent, SpaceTopologyPayload};
+use atlas::graph::{CanonicalProcessor, GraphState, TransitiveProcessor};
+use hermes_schema::pb::space::{HermesCreateSpace, HermesSpaceTrustExtension};
+use libfuzzer_sys::fuzz_target;
+use prost::Message;
+
+fn next_event(input: &mut &[u8]) -> Option<SpaceTopologyEvent> {
+    let (&selector, rest) = input.split_first()?;
+    *input = rest;
+
+    let event = if selector % 2 == 0 {
+        HermesCreateSpace::decode_length_delimited(&mut *input)
+            .map_err(DecodeError::from)
+            .and_then(|message| SpaceTopologyEvent::try_from(&message))
+    } else {
+        HermesSpaceTrustExtension::decode_length_delimited(&mut *input)
+            .map_err(DecodeError::from)
+            .and_then(|message| SpaceTopologyEvent::try_from(&message))
+    };
+
+    event.ok()
+}
+
+fuzz_target!(|data: &[u8]| {
+    let mut input = data;
+    let mut state = GraphState::new();
+    let mut transitive = TransitiveProcessor::new();
+    let mut canonical: Option<CanonicalProcessor> = None;
+
+    while let Some(event) = next_event(&mut input) {
+        // The first created space becomes the canonical root
+        if canonical.is_none() {
+            if let SpaceTopologyPayload::SpaceCreated(created) = &event.payload {
+                canonical = Some(CanonicalProcessor::new(created.space_id));
+            }
+        }
+
+        transitive.handle_event(&event, &state);
+        state.apply_event(&event);
+
+        if let Some(processor) = canonical.as_mut() {
+            processor.compute(&state, &mut transitive);
+        }
+    }
+});
@@ -0,0 +1,421 @@
+//! Rate-of-change anomaly detection on trust events
+//!
+//! Flags spaces that create trust edges at an abnormal rate. Each space has a
+//! sliding window of recent trust extension timestamps; when the number of
+//! edges in the window exceeds the configured threshold an anomaly is raised.
+//!
+//! After raising an anomaly for a space, further anomalies for that space are
+//! suppressed for one window length so a single burst produces a single alert.
+//!
+//! Windows are measured against the latest block timestamp seen, so a block
+//! with an out-of-order timestamp never moves the window backwards. Late edges
+//! still inside the window are counted; late edges older than the window are
+//! ignored. Spaces whose window has emptied and whose suppression has expired
+//! are evicted once per window length to keep memory bounded.
+//!
+//! # Example
+//!
+//! ```
+//! use atlas::anomaly::{AnomalyConfig, TrustRateDetector};
+//!
+//! let config = AnomalyConfig::default()
+//!     .with_window_seconds(600)
+//!     .with_max_edges(20);
+//! let mut detector = TrustRateDetector::new(config);
+//! # let events: Vec<atlas::events::SpaceTopologyEvent> = Vec::new();
+//!
+//! for event in &events {
+//!     if let Some(anomaly) = detector.observe(event) {
+//!         println!("space created {} edges", anomaly.edge_count);
+//!     }
+//! }
+//! ```
+
+use crate::events::{BlockMetadata, SpaceId, SpaceTopologyEvent, SpaceTopologyPayload};
+use std::collections::{HashMap, VecDeque};
+
+/// Configuration for trust rate anomaly detection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnomalyConfig {
+    /// Length of the sliding window in seconds
+    pub window_seconds: u64,
+
+    /// Maximum number of trust edges a space may create within the window
+    pub max_edges: usize,
+}
+
+impl Default for AnomalyConfig {
+    fn default() -> Self {
+        Self {
+            window_seconds: 3600,
+            max_edges: 50,
+        }
+    }
+}
+
+impl AnomalyConfig {
+    /// Set the sliding window length in seconds
+    pub fn with_window_seconds(mut self, window_seconds: u64) -> Self {
+        self.window_seconds = window_seconds;
+        self
+    }
+
+    /// Set the maximum number of edges allowed within the window
+    pub fn with_max_edges(mut self, max_edges: usize) -> Self {
+        self.max_edges = max_edges;
+        self
+    }
+}
+
+/// A space that exceeded the trust edge rate threshold
+#[derive(Debug, Clone)]
+pub struct TrustRateAnomaly {
+    /// The space extending trust at an abnormal rate
+    pub space_id: SpaceId,
+
+    /// Number of edges created within the window, including the triggering edge
+    pub edge_count: usize,
+
+    /// Length of the sliding window in seconds
+    pub window_seconds: u64,
+
+    /// Maximum number of edges allowed within the window
+    pub threshold: usize,
+
+    /// Block metadata from the event that crossed the threshold
+    pub meta: BlockMetadata,
+}
+
+/// Counters describing detector activity
+#[derive(Debug, Clone, Default)]
+pub struct AnomalyStats {
+    /// Number of trust events observed
+    pub events_observed: u64,
+
+    /// Number of anomalies raised
+    pub anomalies_raised: u64,
+
+    /// Number of spaces currently tracked
+    pub tracked_spaces: usize,
+}
+
+/// Sliding-window detector for abnormal trust edge creation rates
+#[derive(Debug)]
+pub struct TrustRateDetector {
+    config: AnomalyConfig,
+
+    /// Recent trust extension timestamps per source space (oldest first)
+    windows: HashMap<SpaceId, VecDeque<u64>>,
+
+    /// Timestamp until which further anomalies for a space are suppressed
+    suppressed_until: HashMap<SpaceId, u64>,
+
+    /// Latest block timestamp observed, used as the end of every window
+    latest_timestamp: u64,
+
+    /// Timestamp of the last sweep for idle spaces
+    last_sweep: u64,
+
+    events_observed: u64,
+    anomalies_raised: u64,
+}
+
+impl TrustRateDetector {
+    /// Create a new detector with the given configuration
+    pub fn new(config: AnomalyConfig) -> Self {
+        Self {
+            config,
+            windows: HashMap::new(),
+            suppressed_until: HashMap::new(),
+            latest_timestamp: 0,
+            last_sweep: 0,
+            events_observed: 0,
+            anomalies_raised: 0,
+        }
+    }
+
+    /// Get the detector configuration
+    pub fn config(&self) -> &AnomalyConfig {
+        &self.config
+    }
+
+    /// Observe a topology event
+    ///
+    /// Returns `Some(anomaly)` if the event pushed its source space over the
+    /// threshold. Non-trust events are ignored.
+    pub fn observe(&mut self, event: &SpaceTopologyEvent) -> Option<TrustRateAnomaly> {
+        let extended = match &event.payload {
+            SpaceTopologyPayload::TrustExtended(extended) => extended,
+            SpaceTopologyPayload::SpaceCreated(_) => return None,
+        };
+
+        self.events_observed += 1;
+
+        let source = extended.source_space_id;
+        let timestamp = event.meta.block_timestamp;
+        self.latest_timestamp = self.latest_timestamp.max(timestamp);
+        let now = self.latest_timestamp;
+        let window_start = now.saturating_sub(self.config.window_seconds);
+
+        if now >= self.last_sweep.saturating_add(self.config.window_seconds) {
+            self.evict_idle(window_start, now);
+        }
+
+        // A late edge from before the window can never contribute to a burst
+        if timestamp < window_start {
+            return None;
+        }
+
+        let window = self.windows.entry(source).or_default();
+        while window.front().is_some_and(|ts| *ts < window_start) {
+            window.pop_front();
+        }
+        // Keep the window sorted so trimming stays correct for late edges
+        let position = window.partition_point(|ts| *ts <= timestamp);
+        window.insert(position, timestamp);
+
+        if window.len() <= self.config.max_edges {
+            return None;
+        }
+
+        if self
+            .suppressed_until
+            .get(&source)
+            .is_some_and(|until| now < *until)
+        {
+            return None;
+        }
+
+        self.suppressed_until
+            .insert(source, now.saturating_add(self.config.window_seconds));
+        self.anomalies_raised += 1;
+
+        Some(TrustRateAnomaly {
+            space_id: source,
+            edge_count: window.len(),
+            window_seconds: self.config.window_seconds,
+            threshold: self.config.max_edges,
+            meta: event.meta.clone(),
+        })
+    }
+
+    /// Drop spaces with no edges in the window and expired suppressions
+    fn evict_idle(&mut self, window_start: u64, now: u64) {
+        self.windows
+            .retain(|_, window| window.back().is_some_and(|ts| *ts >= window_start));
+        self.suppressed_until.retain(|_, until| now < *until);
+        self.last_sweep = now;
+    }
+
+    /// Get detector statistics
+    pub fn stats(&self) -> AnomalyStats {
+        AnomalyStats {
+            events_observed: self.events_observed,
+            anomalies_raised: self.anomalies_raised,
+            tracked_spaces: self.windows.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{SpaceCreated, SpaceType, TrustExtended, TrustExtension};
+
+    fn make_space_id(n: u8) -> SpaceId {
+        let mut id = [0u8; 16];
+        id[15] = n;
+        id
+    }
+
+    fn make_block_meta(timestamp: u64) -> BlockMetadata {
+        BlockMetadata {
+            block_number: timestamp / 12,
+            block_timestamp: timestamp,
+            tx_hash: format!("0x{:064x}", timestamp),
+            cursor: format!("cursor_{}", timestamp),
+        }
+    }
+
+    fn make_verified_event(source: SpaceId, target: SpaceId, timestamp: u64) -> SpaceTopologyEvent {
+        SpaceTopologyEvent {
+            meta: make_block_meta(timestamp),
+            payload: SpaceTopologyPayload::TrustExtended(TrustExtended {
+                source_space_id: source,
+                extension: TrustExtension::Verified {
+                    target_space_id: target,
+                },
+            }),
+        }
+    }
+
+    fn detector(window_seconds: u64, max_edges: usize) -> TrustRateDetector {
+        TrustRateDetector::new(
+            AnomalyConfig::default()
+                .with_window_seconds(window_seconds)
+                .with_max_edges(max_edges),
+        )
+    }
+
+    #[test]
+    fn test_below_threshold_no_anomaly() {
+        let mut detector = detector(100, 3);
+        let source = make_space_id(1);
+
+        for i in 0..3 {
+            let event = make_verified_event(source, make_space_id(10 + i), 10 * i as u64);
+            assert!(detector.observe(&event).is_none());
+        }
+    }
+
+    #[test]
+    fn test_exceeding_threshold_raises_anomaly() {
+        let mut detector = detector(100, 3);
+        let source = make_space_id(1);
+
+        for i in 0..3 {
+            detector.observe(&make_verified_event(
+                source,
+                make_space_id(10 + i),
+                i as u64,
+            ));
+        }
+
+        let anomaly = detector
+            .observe(&make_verified_event(source, make_space_id(20), 5))
+            .unwrap();
+
+        assert_eq!(anomaly.space_id, source);
+        assert_eq!(anomaly.edge_count, 4);
+        assert_eq!(anomaly.threshold, 3);
+        assert_eq!(anomaly.window_seconds, 100);
+        assert_eq!(anomaly.meta.block_timestamp, 5);
+        assert_eq!(detector.stats().anomalies_raised, 1);
+    }
+
+    #[test]
+    fn test_old_edges_slide_out_of_window() {
+        let mut detector = detector(100, 3);
+        let source = make_space_id(1);
+
+        // Four edges spread far enough apart that at most three are in the window
+        for (i, ts) in [0u64, 60, 120, 180].iter().enumerate() {
+            let event = make_verified_event(source, make_space_id(10 + i as u8), *ts);
+            assert!(detector.observe(&event).is_none());
+        }
+    }
+
+    #[test]
+    fn test_anomaly_suppressed_within_window() {
+        let mut detector = detector(100, 1);
+        let source = make_space_id(1);
+
+        detector.observe(&make_verified_event(source, make_space_id(10), 0));
+        assert!(detector
+            .observe(&make_verified_event(source, make_space_id(11), 1))
+            .is_some());
+
+        // Still bursting, but within the suppression period
+        assert!(detector
+            .observe(&make_verified_event(source, make_space_id(12), 2))
+            .is_none());
+
+        // After the suppression period a continued burst alerts again
+        detector.observe(&make_verified_event(source, make_space_id(13), 150));
+        assert!(detector
+            .observe(&make_verified_event(source, make_space_id(14), 151))
+            .is_some());
+        assert_eq!(detector.stats().anomalies_raised, 2);
+    }
+
+    #[test]
+    fn test_spaces_tracked_independently() {
+        let mut detector = detector(100, 1);
+        let a = make_space_id(1);
+        let b = make_space_id(2);
+
+        assert!(detector
+            .observe(&make_verified_event(a, make_space_id(10), 0))
+            .is_none());
+        assert!(detector
+            .observe(&make_verified_event(b, make_space_id(10), 1))
+            .is_none());
+        assert_eq!(detector.stats().tracked_spaces, 2);
+    }
+
+    #[test]
+    fn test_idle_spaces_evicted() {
+        let mut detector = detector(100, 1);
+        let a = make_space_id(1);
+        let b = make_space_id(2);
+
+        detector.observe(&make_verified_event(a, make_space_id(10), 0));
+        assert!(detector
+            .observe(&make_verified_event(a, make_space_id(11), 1))
+            .is_some());
+        assert_eq!(detector.suppressed_until.len(), 1);
+
+        // A's window has emptied and its suppression has expired
+        detector.observe(&make_verified_event(b, make_space_id(10), 500));
+        assert_eq!(detector.stats().tracked_spaces, 1);
+        assert!(!detector.windows.contains_key(&a));
+        assert!(detector.suppressed_until.is_empty());
+    }
+
+    #[test]
+    fn test_out_of_order_timestamps() {
+        let mut detector = detector(100, 2);
+        let source = make_space_id(1);
+
+        detector.observe(&make_verified_event(source, make_space_id(10), 300));
+        detector.observe(&make_verified_event(source, make_space_id(11), 301));
+
+        // Late edge from before the window is not counted
+        assert!(detector
+            .observe(&make_verified_event(source, make_space_id(12), 50))
+            .is_none());
+        assert_eq!(detector.windows[&source].len(), 2);
+
+        // Late edge inside the window is counted in order
+        let anomaly = detector
+            .observe(&make_verified_event(source, make_space_id(13), 250))
+            .unwrap();
+        assert_eq!(anomaly.edge_count, 3);
+        assert_eq!(
+            detector.windows[&source]
+                .iter()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![250, 300, 301]
+        );
+
+        // The window still slides by the latest timestamp
+        detector.observe(&make_verified_event(source, make_space_id(14), 360));
+        assert_eq!(
+            detector.windows[&source]
+                .iter()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![300, 301, 360]
+        );
+    }
+
+    #[test]
+    fn test_space_created_ignored() {
+        let mut detector = detector(100, 0);
+        let event = SpaceTopologyEvent {
+            meta: make_block_meta(0),
+            payload: SpaceTopologyPayload::SpaceCreated(SpaceCreated {
+                space_id: make_space_id(1),
+                topic_id: make_space_id(2),
+                space_type: SpaceType::Dao {
+                    initial_editors: vec![],
+                    initial_members: vec![],
+                },
+            }),
+        };
+
+        assert!(detector.observe(&event).is_none());
+        assert_eq!(detector.stats().events_observed, 0);
+    }
+}
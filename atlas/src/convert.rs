@@ -90,12 +90,18 @@ impl From<&mock_substream::TrustExtended> for SpaceTopologyEvent {
 /// Convert a MockEvent to an optional SpaceTopologyEvent.
 ///
 /// Returns `Some(event)` for SpaceCreated and TrustExtended events.
-/// Returns `None` for EditPublished events (Atlas only processes topology).
+/// Returns `None` for EditPublished and DAO governance events (Atlas only
+/// processes topology) and for UndoSignal events (reorg rollback is not
+/// supported yet).
 pub fn convert_mock_event(event: &mock_substream::MockEvent) -> Option<SpaceTopologyEvent> {
     match event {
         mock_substream::MockEvent::SpaceCreated(space) => Some(SpaceTopologyEvent::from(space)),
         mock_substream::MockEvent::TrustExtended(trust) => Some(SpaceTopologyEvent::from(trust)),
         mock_substream::MockEvent::EditPublished(_) => None, // Atlas ignores edits
+        mock_substream::MockEvent::MemberAdded(_)
+        | mock_substream::MockEvent::MemberRemoved(_)
+        | mock_substream::MockEvent::EditorAdded(_) => None,
+        mock_substream::MockEvent::UndoSignal(_) => None,
     }
 }
 
@@ -180,7 +186,7 @@ mod tests {
         let events = convert_mock_blocks(&blocks);
 
         // Should have 18 spaces + 19 trust extensions = 37 topology events
-        // (6 edits are filtered out)
+        // (6 edits and 4 DAO governance events are filtered out)
         assert_eq!(events.len(), 37);
 
         // Verify no edit events came through
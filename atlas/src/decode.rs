@@ -0,0 +1,315 @@
+//! Decoding of Hermes wire messages into Atlas events
+//!
+//! Converts the protobuf messages published by hermes-processor
+//! (`HermesCreateSpace`, `HermesSpaceTrustExtension`) into `SpaceTopologyEvent`s.
+//!
+//! Input comes from the network and is treated as untrusted: every failure is
+//! reported as a `DecodeError`, and decoding never panics.
+
+use crate::events::{
+    BlockMetadata, SpaceCreated, SpaceTopologyEvent, SpaceTopologyPayload, SpaceType,
+    TrustExtended, TrustExtension,
+};
+use hermes_schema::pb::blockchain_metadata::BlockchainMetadata;
+use hermes_schema::pb::space::{
+    hermes_create_space, hermes_space_trust_extension, HermesCreateSpace, HermesSpaceTrustExtension,
+};
+use prost::Message;
+
+/// Error types for decoding operations
+#[derive(Debug)]
+pub enum DecodeError {
+    /// Bytes are not a valid protobuf message
+    Proto(prost::DecodeError),
+    /// An ID field has the wrong length
+    InvalidLength {
+        field: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    /// A required field is missing
+    MissingField(&'static str),
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::Proto(e) => write!(f, "invalid protobuf: {}", e),
+            DecodeError::InvalidLength {
+                field,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "invalid length for {}: expected {} bytes, got {}",
+                field, expected, actual
+            ),
+            DecodeError::MissingField(field) => write!(f, "missing field: {}", field),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DecodeError::Proto(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<prost::DecodeError> for DecodeError {
+    fn from(e: prost::DecodeError) -> Self {
+        DecodeError::Proto(e)
+    }
+}
+
+/// Decode a `HermesCreateSpace` payload into a `SpaceCreated` event
+pub fn decode_space_created(bytes: &[u8]) -> Result<SpaceTopologyEvent, DecodeError> {
+    let message = HermesCreateSpace::decode(bytes)?;
+    SpaceTopologyEvent::try_from(&message)
+}
+
+/// Decode a `HermesSpaceTrustExtension` payload into a `TrustExtended` event
+pub fn decode_trust_extended(bytes: &[u8]) -> Result<SpaceTopologyEvent, DecodeError> {
+    let message = HermesSpaceTrustExtension::decode(bytes)?;
+    SpaceTopologyEvent::try_from(&message)
+}
+
+/// Convert a Hermes BlockchainMetadata to Atlas BlockMetadata
+///
+/// The wire format does not carry a transaction hash, so `tx_hash` is empty.
+impl From<&BlockchainMetadata> for BlockMetadata {
+    fn from(meta: &BlockchainMetadata) -> Self {
+        BlockMetadata {
+            block_number: meta.block_number,
+            block_timestamp: meta.created_at,
+            tx_hash: String::new(),
+            cursor: meta.cursor.clone(),
+        }
+    }
+}
+
+/// Convert a Hermes HermesCreateSpace to Atlas SpaceTopologyEvent
+impl TryFrom<&HermesCreateSpace> for SpaceTopologyEvent {
+    type Error = DecodeError;
+
+    fn try_from(message: &HermesCreateSpace) -> Result<Self, Self::Error> {
+        let meta = message
+            .meta
+            .as_ref()
+            .ok_or(DecodeError::MissingField("meta"))?;
+
+        let space_type = match &message.payload {
+            Some(hermes_create_space::Payload::PersonalSpace(personal)) => SpaceType::Personal {
+                owner: fixed_bytes("owner", &personal.owner)?,
+            },
+            Some(hermes_create_space::Payload::DefaultDaoSpace(dao)) => SpaceType::Dao {
+                initial_editors: fixed_bytes_list("initial_editors", &dao.initial_editors)?,
+                initial_members: fixed_bytes_list("initial_members", &dao.initial_members)?,
+            },
+            None => return Err(DecodeError::MissingField("payload")),
+        };
+
+        Ok(SpaceTopologyEvent {
+            meta: BlockMetadata::from(meta),
+            payload: SpaceTopologyPayload::SpaceCreated(SpaceCreated {
+                space_id: fixed_bytes("space_id", &message.space_id)?,
+                topic_id: fixed_bytes("topic_id", &message.topic_id)?,
+                space_type,
+            }),
+        })
+    }
+}
+
+/// Convert a Hermes HermesSpaceTrustExtension to Atlas SpaceTopologyEvent
+impl TryFrom<&HermesSpaceTrustExtension> for SpaceTopologyEvent {
+    type Error = DecodeError;
+
+    fn try_from(message: &HermesSpaceTrustExtension) -> Result<Self, Self::Error> {
+        let meta = message
+            .meta
+            .as_ref()
+            .ok_or(DecodeError::MissingField("meta"))?;
+
+        let extension = match &message.extension {
+            Some(hermes_space_trust_extension::Extension::Verified(verified)) => {
+                TrustExtension::Verified {
+                    target_space_id: fixed_bytes("target_space_id", &verified.target_space_id)?,
+                }
+            }
+            Some(hermes_space_trust_extension::Extension::Related(related)) => {
+                TrustExtension::Related {
+                    target_space_id: fixed_bytes("target_space_id", &related.target_space_id)?,
+                }
+            }
+            Some(hermes_space_trust_extension::Extension::Subtopic(subtopic)) => {
+                TrustExtension::Subtopic {
+                    target_topic_id: fixed_bytes("target_topic_id", &subtopic.target_topic_id)?,
+                }
+            }
+            None => return Err(DecodeError::MissingField("extension")),
+        };
+
+        Ok(SpaceTopologyEvent {
+            meta: BlockMetadata::from(meta),
+            payload: SpaceTopologyPayload::TrustExtended(TrustExtended {
+                source_space_id: fixed_bytes("source_space_id", &message.source_space_id)?,
+                extension,
+            }),
+        })
+    }
+}
+
+/// Convert a byte slice to a fixed-size array, checking the length
+fn fixed_bytes<const N: usize>(field: &'static str, bytes: &[u8]) -> Result<[u8; N], DecodeError> {
+    bytes.try_into().map_err(|_| DecodeError::InvalidLength {
+        field,
+        expected: N,
+        actual: bytes.len(),
+    })
+}
+
+/// Convert a list of byte vectors to fixed-size arrays
+fn fixed_bytes_list<const N: usize>(
+    field: &'static str,
+    list: &[Vec<u8>],
+) -> Result<Vec<[u8; N]>, DecodeError> {
+    list.iter().map(|bytes| fixed_bytes(field, bytes)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hermes_schema::pb::space::{
+        DefaultDaoSpacePayload, PersonalSpacePayload, VerifiedExtension,
+    };
+
+    fn make_meta() -> BlockchainMetadata {
+        BlockchainMetadata {
+            created_at: 1_700_000_000,
+            created_by: vec![],
+            block_number: 42,
+            cursor: "cursor_42".to_string(),
+        }
+    }
+
+    fn make_id(n: u8) -> Vec<u8> {
+        let mut id = vec![0u8; 16];
+        id[15] = n;
+        id
+    }
+
+    #[test]
+    fn test_decode_dao_space() {
+        let message = HermesCreateSpace {
+            space_id: make_id(1),
+            topic_id: make_id(2),
+            payload: Some(hermes_create_space::Payload::DefaultDaoSpace(
+                DefaultDaoSpacePayload {
+                    initial_editors: vec![make_id(3)],
+                    initial_members: vec![],
+                },
+            )),
+            meta: Some(make_meta()),
+        };
+
+        let event = decode_space_created(&message.encode_to_vec()).unwrap();
+
+        assert_eq!(event.meta.block_number, 42);
+        assert_eq!(event.meta.block_timestamp, 1_700_000_000);
+        assert_eq!(event.meta.cursor, "cursor_42");
+        match event.payload {
+            SpaceTopologyPayload::SpaceCreated(created) => {
+                assert_eq!(created.space_id[15], 1);
+                assert_eq!(created.topic_id[15], 2);
+                match created.space_type {
+                    SpaceType::Dao {
+                        initial_editors, ..
+                    } => assert_eq!(initial_editors.len(), 1),
+                    _ => panic!("Expected DAO space"),
+                }
+            }
+            _ => panic!("Expected SpaceCreated"),
+        }
+    }
+
+    #[test]
+    fn test_decode_verified_trust() {
+        let message = HermesSpaceTrustExtension {
+            source_space_id: make_id(1),
+            extension: Some(hermes_space_trust_extension::Extension::Verified(
+                VerifiedExtension {
+                    target_space_id: make_id(2),
+                },
+            )),
+            meta: Some(make_meta()),
+        };
+
+        let event = decode_trust_extended(&message.encode_to_vec()).unwrap();
+
+        match event.payload {
+            SpaceTopologyPayload::TrustExtended(extended) => {
+                assert_eq!(extended.source_space_id[15], 1);
+                match extended.extension {
+                    TrustExtension::Verified { target_space_id } => {
+                        assert_eq!(target_space_id[15], 2)
+                    }
+                    _ => panic!("Expected Verified extension"),
+                }
+            }
+            _ => panic!("Expected TrustExtended"),
+        }
+    }
+
+    #[test]
+    fn test_decode_invalid_id_length() {
+        let message = HermesCreateSpace {
+            space_id: vec![1, 2, 3],
+            topic_id: make_id(2),
+            payload: Some(hermes_create_space::Payload::PersonalSpace(
+                PersonalSpacePayload {
+                    owner: vec![0u8; 32],
+                },
+            )),
+            meta: Some(make_meta()),
+        };
+
+        let err = decode_space_created(&message.encode_to_vec()).unwrap_err();
+        assert!(matches!(
+            err,
+            DecodeError::InvalidLength {
+                field: "space_id",
+                expected: 16,
+                actual: 3,
+            }
+        ));
+    }
+
+    #[test]
+    fn test_decode_missing_fields() {
+        let message = HermesSpaceTrustExtension {
+            source_space_id: make_id(1),
+            extension: None,
+            meta: Some(make_meta()),
+        };
+        assert!(matches!(
+            decode_trust_extended(&message.encode_to_vec()),
+            Err(DecodeError::MissingField("extension"))
+        ));
+
+        // An empty buffer decodes to a default message with no meta
+        assert!(matches!(
+            decode_space_created(&[]),
+            Err(DecodeError::MissingField("meta"))
+        ));
+    }
+
+    #[test]
+    fn test_decode_garbage() {
+        assert!(matches!(
+            decode_trust_extended(&[0xff, 0xff, 0xff]),
+            Err(DecodeError::Proto(_))
+        ));
+    }
+}
@@ -7,9 +7,9 @@
 //! The canonical graph represents the "trusted" portion of the topology graph,
 //! where trust flows only through explicit edges (Verified, Related).
 
-use super::{hash_tree, GraphState, TransitiveProcessor, TreeNode};
-use crate::events::{SpaceId, SpaceTopologyEvent, SpaceTopologyPayload, TopicId};
-use std::collections::HashSet;
+use super::{hash_tree, EdgeType, GraphState, TransitiveProcessor, TreeNode};
+use crate::events::{SpaceId, SpaceTopologyEvent, SpaceTopologyPayload, TopicId, TrustExtension};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 /// Result of canonical graph computation
 #[derive(Debug, Clone)]
@@ -45,6 +45,99 @@ impl CanonicalGraph {
     pub fn is_empty(&self) -> bool {
         self.flat.len() <= 1
     }
+
+    /// Get the trust path of every canonical space, sorted by space ID
+    ///
+    /// Spaces that appear more than once in the tree use the occurrence with
+    /// the strongest path type, preferring the shallowest at equal strength.
+    pub fn trust_paths(&self) -> Vec<TrustPath> {
+        let mut best: HashMap<SpaceId, TrustPath> = HashMap::new();
+        let mut queue = VecDeque::from([(&self.tree, 0u32, None, EdgeType::Root)]);
+
+        while let Some((node, depth, parent, path_type)) = queue.pop_front() {
+            let improves = best.get(&node.space_id).is_none_or(|current| {
+                (path_rank(path_type), depth) < (path_rank(current.path_type), current.depth)
+            });
+            if improves && self.flat.contains(&node.space_id) {
+                best.insert(
+                    node.space_id,
+                    TrustPath {
+                        space_id: node.space_id,
+                        depth,
+                        parent,
+                        path_type,
+                    },
+                );
+            }
+
+            for child in &node.children {
+                let child_path = if path_rank(child.edge_type) > path_rank(path_type) {
+                    child.edge_type
+                } else {
+                    path_type
+                };
+                queue.push_back((child, depth + 1, Some(node.space_id), child_path));
+            }
+        }
+
+        let mut paths: Vec<TrustPath> = best.into_values().collect();
+        paths.sort_by_key(|path| path.space_id);
+        paths
+    }
+}
+
+/// How a canonical space is reached from the root
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrustPath {
+    /// The canonical space
+    pub space_id: SpaceId,
+
+    /// Number of edges between the root and this space (0 for the root)
+    pub depth: u32,
+
+    /// Space this one is reached from, `None` for the root
+    pub parent: Option<SpaceId>,
+
+    /// Weakest edge type along the path from the root
+    pub path_type: EdgeType,
+}
+
+/// Rank edge types from strongest (root) to weakest (topic)
+fn path_rank(edge_type: EdgeType) -> u8 {
+    match edge_type {
+        EdgeType::Root => 0,
+        EdgeType::Verified => 1,
+        EdgeType::Related => 2,
+        EdgeType::Topic => 3,
+    }
+}
+
+/// Change in the canonical set between two states
+///
+/// Both lists are sorted for deterministic output.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CanonicalDelta {
+    /// Spaces that would join the canonical set
+    pub added: Vec<SpaceId>,
+
+    /// Spaces that would leave the canonical set
+    pub removed: Vec<SpaceId>,
+}
+
+impl CanonicalDelta {
+    /// Compute the delta between two canonical sets
+    pub fn between(before: &HashSet<SpaceId>, after: &HashSet<SpaceId>) -> Self {
+        let mut added: Vec<SpaceId> = after.difference(before).copied().collect();
+        let mut removed: Vec<SpaceId> = before.difference(after).copied().collect();
+        added.sort();
+        removed.sort();
+        Self { added, removed }
+    }
+
+    /// Check if the canonical set is unchanged
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
 }
 
 /// Processor for computing canonical graphs
@@ -59,6 +152,10 @@ pub struct CanonicalProcessor {
     /// Hash of the last computed tree structure
     /// Used to detect changes in tree structure (not just canonical set)
     last_hash: Option<u64>,
+
+    /// Canonical set maintained by `apply_incremental`
+    /// Resynchronized whenever `compute` produces a new graph
+    canonical_set: HashSet<SpaceId>,
 }
 
 impl CanonicalProcessor {
@@ -67,6 +164,7 @@ impl CanonicalProcessor {
         Self {
             root,
             last_hash: None,
+            canonical_set: HashSet::from([root]),
         }
     }
 
@@ -146,9 +244,100 @@ impl CanonicalProcessor {
         }
 
         self.last_hash = Some(new_hash);
+        self.canonical_set.clone_from(&graph.flat);
         Some(graph)
     }
 
+    /// Get the canonical set tracked by incremental mode
+    pub fn canonical_set(&self) -> &HashSet<SpaceId> {
+        &self.canonical_set
+    }
+
+    /// Incrementally update the canonical set for an event
+    ///
+    /// Must be called after the event has been applied to `state`. Only the
+    /// spaces newly reachable through the event's edge are visited, so the cost
+    /// is proportional to the size of the change rather than the whole graph.
+    ///
+    /// Only the canonical set is tracked; topic edges change the tree but never
+    /// the set, so use `compute` periodically to get a full snapshot.
+    pub fn apply_incremental(
+        &mut self,
+        event: &SpaceTopologyEvent,
+        state: &GraphState,
+    ) -> CanonicalDelta {
+        let SpaceTopologyPayload::TrustExtended(extended) = &event.payload else {
+            return CanonicalDelta::default();
+        };
+
+        let target = match &extended.extension {
+            TrustExtension::Verified { target_space_id }
+            | TrustExtension::Related { target_space_id } => *target_space_id,
+            TrustExtension::Subtopic { .. } => return CanonicalDelta::default(),
+        };
+
+        if !self.canonical_set.contains(&extended.source_space_id)
+            || !self.canonical_set.insert(target)
+        {
+            return CanonicalDelta::default();
+        }
+
+        // BFS over explicit edges from the new member, stopping at known members
+        let mut added = Vec::new();
+        let mut queue = VecDeque::from([target]);
+
+        while let Some(current) = queue.pop_front() {
+            added.push(current);
+
+            if let Some(edges) = state.get_explicit_edges(&current) {
+                for (next, _) in edges {
+                    if self.canonical_set.insert(*next) {
+                        queue.push_back(*next);
+                    }
+                }
+            }
+        }
+
+        // Trust is never revoked, so the set only grows
+        added.sort();
+        CanonicalDelta {
+            added,
+            removed: Vec::new(),
+        }
+    }
+
+    /// Preview the canonical set change an event would cause
+    ///
+    /// Computes the delta against a copy of the state, so neither `state` nor
+    /// this processor's change detection is affected. `transitive` is only used
+    /// to read (and cache) the current canonical set.
+    ///
+    /// Events that cannot affect the canonical graph (see `affects_canonical`)
+    /// return an empty delta without copying the state.
+    pub fn simulate(
+        &self,
+        event: &SpaceTopologyEvent,
+        state: &GraphState,
+        transitive: &mut TransitiveProcessor,
+    ) -> CanonicalDelta {
+        let before = &transitive.get_explicit_only(self.root, state).flat;
+
+        if !self.affects_canonical(event, before) {
+            return CanonicalDelta::default();
+        }
+
+        let mut simulated = state.clone();
+        simulated.apply_event(event);
+
+        // Fresh processor: the simulated state must not pollute the real cache
+        let after = TransitiveProcessor::new()
+            .get_explicit_only(self.root, &simulated)
+            .flat
+            .clone();
+
+        CanonicalDelta::between(before, &after)
+    }
+
     /// Collect all topic edges from canonical nodes
     ///
     /// Returns a sorted list of (source, topic_id) pairs for deterministic processing.
@@ -432,6 +621,100 @@ mod tests {
         assert!(graph.tree.node_count() >= 3);
     }
 
+    #[test]
+    fn test_trust_paths() {
+        // Root -verified-> A -related-> B -verified-> C
+        // Root -topic-> topic(B)
+        let mut state = GraphState::new();
+        let root = create_space(&mut state, 1);
+        let a = create_space(&mut state, 2);
+        let b = create_space(&mut state, 3);
+        let c = create_space(&mut state, 4);
+
+        add_verified_edge(&mut state, root, a);
+        state.apply_event(&SpaceTopologyEvent {
+            meta: make_block_meta(),
+            payload: SpaceTopologyPayload::TrustExtended(TrustExtended {
+                source_space_id: a,
+                extension: TrustExtension::Related { target_space_id: b },
+            }),
+        });
+        add_verified_edge(&mut state, b, c);
+
+        let mut transitive = TransitiveProcessor::new();
+        let graph = CanonicalProcessor::new(root)
+            .compute(&state, &mut transitive)
+            .unwrap();
+        let paths: HashMap<SpaceId, TrustPath> = graph
+            .trust_paths()
+            .into_iter()
+            .map(|path| (path.space_id, path))
+            .collect();
+
+        assert_eq!(paths.len(), 4);
+        assert_eq!(paths[&root].depth, 0);
+        assert_eq!(paths[&root].parent, None);
+        assert_eq!(paths[&root].path_type, EdgeType::Root);
+        assert_eq!(paths[&a].path_type, EdgeType::Verified);
+        assert_eq!(paths[&b].depth, 2);
+        assert_eq!(paths[&b].parent, Some(a));
+        assert_eq!(paths[&b].path_type, EdgeType::Related);
+
+        // The weakest edge on the path determines its type
+        assert_eq!(paths[&c].depth, 3);
+        assert_eq!(paths[&c].path_type, EdgeType::Related);
+
+        // A deeper related path wins over a shallower topic one
+        add_topic_edge(&mut state, root, make_topic_id(3));
+        transitive.handle_event(
+            &SpaceTopologyEvent {
+                meta: make_block_meta(),
+                payload: SpaceTopologyPayload::TrustExtended(TrustExtended {
+                    source_space_id: root,
+                    extension: TrustExtension::Subtopic {
+                        target_topic_id: make_topic_id(3),
+                    },
+                }),
+            },
+            &state,
+        );
+        let graph = CanonicalProcessor::new(root)
+            .compute(&state, &mut transitive)
+            .unwrap();
+        let paths: HashMap<SpaceId, TrustPath> = graph
+            .trust_paths()
+            .into_iter()
+            .map(|path| (path.space_id, path))
+            .collect();
+        assert_eq!(paths[&b].depth, 2);
+        assert_eq!(paths[&b].parent, Some(a));
+        assert_eq!(paths[&b].path_type, EdgeType::Related);
+        assert_eq!(paths[&c].depth, 3);
+        assert_eq!(paths[&c].path_type, EdgeType::Related);
+
+        // At equal strength the shallowest occurrence wins
+        let related = SpaceTopologyEvent {
+            meta: make_block_meta(),
+            payload: SpaceTopologyPayload::TrustExtended(TrustExtended {
+                source_space_id: root,
+                extension: TrustExtension::Related { target_space_id: c },
+            }),
+        };
+        transitive.handle_event(&related, &state);
+        state.apply_event(&related);
+        let graph = CanonicalProcessor::new(root)
+            .compute(&state, &mut transitive)
+            .unwrap();
+        let c_path = graph
+            .trust_paths()
+            .into_iter()
+            .find(|path| path.space_id == c)
+            .unwrap();
+        assert_eq!(c_path.depth, 1);
+        assert_eq!(c_path.parent, Some(root));
+        assert_eq!(c_path.path_type, EdgeType::Related);
+    }
+
     #[test]
     fn test_topic_edge_to_non_canonical_member() {
         // Root -> A (explicit)
@@ -611,6 +894,212 @@ mod tests {
         assert_eq!(graph3.len(), 2); // Root + A
     }
 
+    #[test]
+    fn test_simulate_previews_added_spaces() {
+        // Root -> A, and B -> C exists off-graph
+        let mut state = GraphState::new();
+        let root = create_space(&mut state, 1);
+        let a = create_space(&mut state, 2);
+        let b = create_space(&mut state, 3);
+        let c = create_space(&mut state, 4);
+        add_verified_edge(&mut state, root, a);
+        add_verified_edge(&mut state, b, c);
+
+        let mut transitive = TransitiveProcessor::new();
+        let processor = CanonicalProcessor::new(root);
+
+        // What if A verifies B?
+        let event = SpaceTopologyEvent {
+            meta: make_block_meta(),
+            payload: SpaceTopologyPayload::TrustExtended(TrustExtended {
+                source_space_id: a,
+                extension: TrustExtension::Verified { target_space_id: b },
+            }),
+        };
+
+        let delta = processor.simulate(&event, &state, &mut transitive);
+
+        let mut expected = vec![b, c];
+        expected.sort();
+        assert_eq!(delta.added, expected);
+        assert!(delta.removed.is_empty());
+
+        // State is untouched
+        assert!(state.get_explicit_edges(&a).is_none());
+    }
+
+    #[test]
+    fn test_simulate_does_not_affect_change_detection() {
+        let mut state = GraphState::new();
+        let root = create_space(&mut state, 1);
+        let a = create_space(&mut state, 2);
+
+        let mut transitive = TransitiveProcessor::new();
+        let mut processor = CanonicalProcessor::new(root);
+        assert!(processor.compute(&state, &mut transitive).is_some());
+
+        let event = SpaceTopologyEvent {
+            meta: make_block_meta(),
+            payload: SpaceTopologyPayload::TrustExtended(TrustExtended {
+                source_space_id: root,
+                extension: TrustExtension::Verified { target_space_id: a },
+            }),
+        };
+        assert_eq!(
+            processor.simulate(&event, &state, &mut transitive).added,
+            vec![a]
+        );
+
+        // Nothing was applied, so the canonical graph is still unchanged
+        assert!(processor.compute(&state, &mut transitive).is_none());
+    }
+
+    #[test]
+    fn test_simulate_non_canonical_source_is_empty() {
+        let mut state = GraphState::new();
+        let root = create_space(&mut state, 1);
+        let x = create_space(&mut state, 2);
+        let y = create_space(&mut state, 3);
+
+        let mut transitive = TransitiveProcessor::new();
+        let processor = CanonicalProcessor::new(root);
+
+        let event = SpaceTopologyEvent {
+            meta: make_block_meta(),
+            payload: SpaceTopologyPayload::TrustExtended(TrustExtended {
+                source_space_id: x,
+                extension: TrustExtension::Verified { target_space_id: y },
+            }),
+        };
+
+        assert!(processor
+            .simulate(&event, &state, &mut transitive)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_simulate_topic_edge_adds_nothing() {
+        // Topic edges never grant canonicality
+        let mut state = GraphState::new();
+        let root = create_space(&mut state, 1);
+        let _b = create_space(&mut state, 2);
+
+        let mut transitive = TransitiveProcessor::new();
+        let processor = CanonicalProcessor::new(root);
+
+        let event = SpaceTopologyEvent {
+            meta: make_block_meta(),
+            payload: SpaceTopologyPayload::TrustExtended(TrustExtended {
+                source_space_id: root,
+                extension: TrustExtension::Subtopic {
+                    target_topic_id: make_topic_id(2),
+                },
+            }),
+        };
+
+        assert!(processor
+            .simulate(&event, &state, &mut transitive)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_incremental_adds_reachable_subgraph() {
+        // B -> C exists before Root -> A -> B is connected
+        let mut state = GraphState::new();
+        let root = create_space(&mut state, 1);
+        let a = create_space(&mut state, 2);
+        let b = create_space(&mut state, 3);
+        let c = create_space(&mut state, 4);
+        let mut processor = CanonicalProcessor::new(root);
+
+        add_verified_edge(&mut state, b, c);
+
+        let event = SpaceTopologyEvent {
+            meta: make_block_meta(),
+            payload: SpaceTopologyPayload::TrustExtended(TrustExtended {
+                source_space_id: root,
+                extension: TrustExtension::Verified { target_space_id: a },
+            }),
+        };
+        state.apply_event(&event);
+        assert_eq!(processor.apply_incremental(&event, &state).added, vec![a]);
+
+        let event = SpaceTopologyEvent {
+            meta: make_block_meta(),
+            payload: SpaceTopologyPayload::TrustExtended(TrustExtended {
+                source_space_id: a,
+                extension: TrustExtension::Related { target_space_id: b },
+            }),
+        };
+        state.apply_event(&event);
+        let delta = processor.apply_incremental(&event, &state);
+
+        let mut expected = vec![b, c];
+        expected.sort();
+        assert_eq!(delta.added, expected);
+        assert!(delta.removed.is_empty());
+        assert_eq!(processor.canonical_set().len(), 4);
+
+        // Re-adding an existing edge changes nothing
+        state.apply_event(&event);
+        assert!(processor.apply_incremental(&event, &state).is_empty());
+    }
+
+    #[test]
+    fn test_incremental_matches_full_recomputation() {
+        use crate::convert::convert_mock_blocks;
+        use mock_substream::test_topology;
+
+        let events = convert_mock_blocks(&test_topology::generate());
+        let mut state = GraphState::new();
+        let mut processor = CanonicalProcessor::new(test_topology::ROOT_SPACE_ID);
+
+        for event in &events {
+            state.apply_event(event);
+            processor.apply_incremental(event, &state);
+
+            let mut transitive = TransitiveProcessor::new();
+            let full = &transitive
+                .get_explicit_only(test_topology::ROOT_SPACE_ID, &state)
+                .flat;
+            assert_eq!(processor.canonical_set(), full);
+        }
+
+        assert_eq!(processor.canonical_set().len(), 11);
+    }
+
+    #[test]
+    fn test_canonical_set_matches_golden() {
+        use crate::convert::convert_mock_blocks;
+        use mock_substream::{golden, test_topology};
+
+        let mut state = GraphState::new();
+        for event in convert_mock_blocks(&test_topology::generate()) {
+            state.apply_event(&event);
+        }
+        let mut processor = CanonicalProcessor::new(test_topology::ROOT_SPACE_ID);
+        let mut transitive = TransitiveProcessor::new();
+        processor.compute(&state, &mut transitive).unwrap();
+
+        golden::assert_canonical_set(&test_topology::ROOT_SPACE_ID, processor.canonical_set());
+    }
+
+    #[test]
+    fn test_compute_resyncs_incremental_set() {
+        let mut state = GraphState::new();
+        let root = create_space(&mut state, 1);
+        let a = create_space(&mut state, 2);
+        let mut processor = CanonicalProcessor::new(root);
+        let mut transitive = TransitiveProcessor::new();
+
+        // Edge applied without going through apply_incremental
+        add_verified_edge(&mut state, root, a);
+        assert!(!processor.canonical_set().contains(&a));
+
+        processor.compute(&state, &mut transitive).unwrap();
+        assert!(processor.canonical_set().contains(&a));
+    }
+
     #[test]
     fn test_multiple_spaces_same_topic() {
         // Multiple spaces announce the same topic
@@ -13,7 +13,7 @@
 //! println!("GraphState uses {} bytes", breakdown.total_bytes);
 //! ```
 
-use super::{EdgeType, GraphState, TransitiveGraph, TreeNode};
+use super::{EdgeMetadata, EdgeType, GraphState, TransitiveGraph, TreeNode};
 use crate::events::{SpaceId, TopicId};
 use std::collections::{HashMap, HashSet};
 use std::mem;
@@ -32,6 +32,7 @@ pub struct GraphStateMemory {
     pub explicit_edges_bytes: usize,
     pub topic_edges_bytes: usize,
     pub topic_edge_sources_bytes: usize,
+    pub edge_metadata_bytes: usize,
 }
 
 /// Calculate memory usage of a GraphState
@@ -49,19 +50,24 @@ pub fn graph_state_size(state: &GraphState) -> GraphStateMemory {
     let topic_edge_sources_bytes =
         hashmap_with_hashset_size::<TopicId, SpaceId>(&state.topic_edge_sources);
 
+    let edge_metadata_bytes = hashmap_with_edge_metadata_size(&state.explicit_edge_metadata)
+        + hashmap_with_edge_metadata_size(&state.topic_edge_metadata);
+
     GraphStateMemory {
         total_bytes: spaces_bytes
             + space_topics_bytes
             + topic_spaces_bytes
             + explicit_edges_bytes
             + topic_edges_bytes
-            + topic_edge_sources_bytes,
+            + topic_edge_sources_bytes
+            + edge_metadata_bytes,
         spaces_bytes,
         space_topics_bytes,
         topic_spaces_bytes,
         explicit_edges_bytes,
         topic_edges_bytes,
         topic_edge_sources_bytes,
+        edge_metadata_bytes,
     }
 }
 
@@ -163,6 +169,13 @@ fn hashmap_with_vec_size(map: &HashMap<SpaceId, Vec<(SpaceId, EdgeType)>>) -> us
     table_size + vecs_size
 }
 
+/// Estimate heap size of a HashMap<K, EdgeMetadata>, including tx hash strings
+fn hashmap_with_edge_metadata_size<K>(map: &HashMap<K, EdgeMetadata>) -> usize {
+    let table_size = map.capacity() * (mem::size_of::<K>() + mem::size_of::<EdgeMetadata>() + 16);
+    let strings_size: usize = map.values().map(|m| m.tx_hash.capacity()).sum();
+    table_size + strings_size
+}
+
 // ============================================================================
 // Convenience functions
 // ============================================================================
@@ -4,18 +4,26 @@
 //! - `TreeNode`: Represents a node in a tree with edge metadata
 //! - `TransitiveGraph`: Result of transitive closure computation
 //! - `CanonicalGraph`: Result of canonical graph computation from a root
+//! - `CanonicalDelta`: Change in the canonical set between two states
 //! - `GraphState`: In-memory representation of the topology graph
+//! - `EdgeMetadata`: Provenance (creator, block) of a trust edge
+//! - `IslandPruner`: Archives stale non-canonical islands to a `ColdStore`
 //! - `memory`: Functions for estimating heap memory usage
 
 mod canonical;
 mod hash;
 pub mod memory;
+mod prune;
 mod state;
 mod transitive;
 mod tree;
 
-pub use canonical::{CanonicalGraph, CanonicalProcessor};
+pub use canonical::{CanonicalDelta, CanonicalGraph, CanonicalProcessor, TrustPath};
 pub use hash::{hash_tree, DefaultTreeHasher, TreeHasher};
-pub use state::GraphState;
+pub use prune::{
+    ColdStore, DiskColdStore, IslandId, IslandPruner, MemoryColdStore, PruneConfig, PruneReport,
+    PruneStats,
+};
+pub use state::{EdgeMetadata, GraphState};
 pub use transitive::{TransitiveCache, TransitiveGraph, TransitiveProcessor};
 pub use tree::{EdgeType, TreeNode};
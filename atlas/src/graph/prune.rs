@@ -0,0 +1,914 @@
+//! Pruning of stale non-canonical islands
+//!
+//! Long-running deployments accumulate spaces that are not reachable from the
+//! root and see no further activity. `IslandPruner` finds connected components
+//! of the graph that do not contain the root and have had no events for a
+//! configured number of blocks, and moves them out of `GraphState` into a
+//! `ColdStore`.
+//!
+//! Archived islands are restored on demand: before applying an event, call
+//! `rehydrate` so that any island the event touches is merged back into the
+//! state first.
+//!
+//! `DiskColdStore` keeps each island in its own file, so archived islands do
+//! not take memory. State snapshots record the pruner's index with `archive`
+//! instead of the islands themselves; after restoring one, pass the index to
+//! `restore_archive`, and call `retain_archived` once each new snapshot is
+//! saved.
+//!
+//! Connectivity ignores edge direction. Explicit edges connect their source
+//! and target, and topic edges connect their source with every space that
+//! announces the topic. Because the root's component is never pruned, pruning
+//! never changes the canonical graph.
+//!
+//! # Example
+//!
+//! ```
+//! use atlas::graph::{GraphState, IslandPruner, PruneConfig, TransitiveProcessor};
+//! # let root = [0u8; 16];
+//! # let events: Vec<atlas::events::SpaceTopologyEvent> = Vec::new();
+//!
+//! let mut state = GraphState::new();
+//! let mut transitive = TransitiveProcessor::new();
+//! let mut pruner = IslandPruner::new(root, PruneConfig::default().with_stale_blocks(50_000));
+//!
+//! for event in &events {
+//!     pruner.rehydrate(event, &mut state)?;
+//!     pruner.observe(event);
+//!     transitive.handle_event(event, &state);
+//!     state.apply_event(event);
+//! }
+//!
+//! let report = pruner.prune(&mut state, &mut transitive)?;
+//! println!("archived {} spaces", report.spaces);
+//! # Ok::<(), atlas::snapshot::SnapshotError>(())
+//! ```
+
+use super::{GraphState, TransitiveProcessor};
+use crate::events::{SpaceId, SpaceTopologyEvent, SpaceTopologyPayload, TopicId, TrustExtension};
+use crate::snapshot::{
+    write_durably, ArchiveRecord, ArchivedSpaceRecord, ArchivedTopicRecord, GraphSnapshot,
+    SnapshotError,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Identifier of an archived island
+pub type IslandId = u64;
+
+/// Storage for archived islands
+///
+/// Implementations may keep islands anywhere (memory, disk, object storage).
+/// `IslandPruner` keeps its own index of which spaces and topics each island
+/// holds, so a store only needs keyed put and take. Islands are serialized
+/// like snapshots, so failures are reported as `SnapshotError`.
+pub trait ColdStore {
+    /// Store an island
+    fn put(&mut self, id: IslandId, island: &GraphState) -> Result<(), SnapshotError>;
+
+    /// Remove and return an island
+    ///
+    /// A store may keep the island's data until `retain` drops it, so that a
+    /// snapshot saved while the island was archived can still be restored.
+    fn take(&mut self, id: IslandId) -> Result<Option<GraphState>, SnapshotError>;
+
+    /// Drop every island not in `ids`, including data kept for taken islands
+    ///
+    /// Called once a snapshot archiving exactly `ids` is durable.
+    fn retain(&mut self, ids: &HashSet<IslandId>) -> Result<(), SnapshotError>;
+
+    /// Number of islands currently stored
+    fn len(&self) -> usize;
+
+    /// Whether the store is empty
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Cold store that keeps archived islands in memory
+///
+/// Archived islands no longer take part in graph traversal or transitive
+/// caching, but still occupy memory, and are lost on restart. Suitable for
+/// tests and for deployments without state snapshots.
+#[derive(Debug, Default)]
+pub struct MemoryColdStore {
+    islands: HashMap<IslandId, GraphState>,
+}
+
+impl MemoryColdStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ColdStore for MemoryColdStore {
+    fn put(&mut self, id: IslandId, island: &GraphState) -> Result<(), SnapshotError> {
+        self.islands.insert(id, island.clone());
+        Ok(())
+    }
+
+    fn take(&mut self, id: IslandId) -> Result<Option<GraphState>, SnapshotError> {
+        Ok(self.islands.remove(&id))
+    }
+
+    fn retain(&mut self, ids: &HashSet<IslandId>) -> Result<(), SnapshotError> {
+        self.islands.retain(|id, _| ids.contains(id));
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.islands.len()
+    }
+}
+
+/// Cold store that keeps each archived island in its own file
+///
+/// Islands are written as snapshot JSON to `island-<id>.json` in a directory
+/// and read back when taken. Files of taken islands stay on disk until
+/// `retain` drops them.
+#[derive(Debug)]
+pub struct DiskColdStore {
+    dir: PathBuf,
+    /// Islands currently stored, excluding taken ones
+    islands: HashSet<IslandId>,
+}
+
+impl DiskColdStore {
+    /// Open a store in `dir`, creating the directory if needed
+    ///
+    /// Islands already in the directory, e.g. from before a restart, are
+    /// part of the store.
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, SnapshotError> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        let islands = stored_islands(&dir)?.into_iter().collect();
+        Ok(Self { dir, islands })
+    }
+
+    /// Get the directory islands are stored in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Path of the file holding an island
+    fn island_path(&self, id: IslandId) -> PathBuf {
+        self.dir.join(format!("island-{}.json", id))
+    }
+}
+
+impl ColdStore for DiskColdStore {
+    fn put(&mut self, id: IslandId, island: &GraphState) -> Result<(), SnapshotError> {
+        write_durably(
+            &self.island_path(id),
+            &serde_json::to_vec(&island.to_snapshot())?,
+        )?;
+        self.islands.insert(id);
+        Ok(())
+    }
+
+    fn take(&mut self, id: IslandId) -> Result<Option<GraphState>, SnapshotError> {
+        if !self.islands.contains(&id) {
+            return Ok(None);
+        }
+
+        let bytes = fs::read(self.island_path(id))?;
+        let island = GraphState::restore(&GraphSnapshot::from_slice(&bytes)?);
+        self.islands.remove(&id);
+        Ok(Some(island))
+    }
+
+    fn retain(&mut self, ids: &HashSet<IslandId>) -> Result<(), SnapshotError> {
+        self.islands.retain(|id| ids.contains(id));
+        for id in stored_islands(&self.dir)? {
+            if !ids.contains(&id) {
+                fs::remove_file(self.island_path(id))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.islands.len()
+    }
+}
+
+/// IDs of the island files in a directory
+fn stored_islands(dir: &Path) -> io::Result<Vec<IslandId>> {
+    let mut ids = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let name = entry?.file_name();
+        let id: Option<IslandId> = name
+            .to_str()
+            .and_then(|name| name.strip_prefix("island-"))
+            .and_then(|name| name.strip_suffix(".json"))
+            .and_then(|id| id.parse().ok());
+        ids.extend(id);
+    }
+    Ok(ids)
+}
+
+/// Configuration for island pruning
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PruneConfig {
+    /// Number of blocks without events after which an island is archived
+    pub stale_blocks: u64,
+}
+
+impl Default for PruneConfig {
+    fn default() -> Self {
+        Self {
+            stale_blocks: 100_000,
+        }
+    }
+}
+
+impl PruneConfig {
+    /// Set the number of inactive blocks after which an island is archived
+    pub fn with_stale_blocks(mut self, stale_blocks: u64) -> Self {
+        self.stale_blocks = stale_blocks;
+        self
+    }
+}
+
+/// Result of a single pruning pass
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PruneReport {
+    /// Number of islands archived
+    pub islands: usize,
+
+    /// Number of spaces archived
+    pub spaces: usize,
+}
+
+/// Counters describing pruner activity
+#[derive(Debug, Clone, Default)]
+pub struct PruneStats {
+    /// Total islands archived
+    pub islands_archived: u64,
+
+    /// Total islands restored into the graph state
+    pub islands_rehydrated: u64,
+
+    /// Islands currently in cold storage
+    pub archived_islands: usize,
+
+    /// Spaces currently in cold storage
+    pub archived_spaces: usize,
+}
+
+/// Archives stale non-canonical islands and restores them on demand
+#[derive(Debug)]
+pub struct IslandPruner<S: ColdStore = MemoryColdStore> {
+    root: SpaceId,
+    config: PruneConfig,
+    store: S,
+
+    /// Last block in which each space was involved in an event
+    last_activity: HashMap<SpaceId, u64>,
+
+    /// Highest block observed
+    current_block: u64,
+
+    /// First block observed, used as the activity of spaces never observed
+    first_block: Option<u64>,
+
+    /// Archived space -> island holding it
+    archived_spaces: HashMap<SpaceId, IslandId>,
+
+    /// Topic -> archived islands with a space announcing it
+    archived_topics: HashMap<TopicId, HashSet<IslandId>>,
+
+    next_island_id: IslandId,
+    islands_archived: u64,
+    islands_rehydrated: u64,
+}
+
+impl IslandPruner<MemoryColdStore> {
+    /// Create a pruner that archives into memory
+    pub fn new(root: SpaceId, config: PruneConfig) -> Self {
+        Self::with_store(root, config, MemoryColdStore::new())
+    }
+}
+
+impl<S: ColdStore> IslandPruner<S> {
+    /// Create a pruner that archives into the given store
+    pub fn with_store(root: SpaceId, config: PruneConfig, store: S) -> Self {
+        Self {
+            root,
+            config,
+            store,
+            last_activity: HashMap::new(),
+            current_block: 0,
+            first_block: None,
+            archived_spaces: HashMap::new(),
+            archived_topics: HashMap::new(),
+            next_island_id: 0,
+            islands_archived: 0,
+            islands_rehydrated: 0,
+        }
+    }
+
+    /// Get the pruner configuration
+    pub fn config(&self) -> &PruneConfig {
+        &self.config
+    }
+
+    /// Get the cold store
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Check whether a space is currently archived
+    pub fn is_archived(&self, space_id: &SpaceId) -> bool {
+        self.archived_spaces.contains_key(space_id)
+    }
+
+    /// Get the index of archived islands, for saving with a state snapshot
+    ///
+    /// The islands themselves stay in the cold store.
+    pub fn archive(&self) -> ArchiveRecord {
+        let mut spaces: Vec<ArchivedSpaceRecord> = self
+            .archived_spaces
+            .iter()
+            .map(|(space_id, island)| ArchivedSpaceRecord {
+                space_id: *space_id,
+                island: *island,
+            })
+            .collect();
+        spaces.sort_by_key(|record| record.space_id);
+
+        let mut topics: Vec<ArchivedTopicRecord> = self
+            .archived_topics
+            .iter()
+            .flat_map(|(topic_id, islands)| {
+                islands.iter().map(|island| ArchivedTopicRecord {
+                    topic_id: *topic_id,
+                    island: *island,
+                })
+            })
+            .collect();
+        topics.sort_by_key(|record| (record.topic_id, record.island));
+
+        ArchiveRecord {
+            next_island_id: self.next_island_id,
+            spaces,
+            topics,
+        }
+    }
+
+    /// Restore the index of archived islands from a state snapshot
+    ///
+    /// Islands in the cold store that the snapshot does not archive are
+    /// dropped. Fails if the store is missing an archived island.
+    pub fn restore_archive(&mut self, archive: &ArchiveRecord) -> Result<(), SnapshotError> {
+        self.next_island_id = archive.next_island_id;
+        self.archived_spaces = archive
+            .spaces
+            .iter()
+            .map(|record| (record.space_id, record.island))
+            .collect();
+        self.archived_topics.clear();
+        for record in &archive.topics {
+            self.archived_topics
+                .entry(record.topic_id)
+                .or_default()
+                .insert(record.island);
+        }
+
+        let ids = self.archived_islands();
+        self.store.retain(&ids)?;
+        if self.store.len() < ids.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!(
+                    "cold store holds {} of {} archived islands",
+                    self.store.len(),
+                    ids.len()
+                ),
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Drop islands from the cold store that are no longer archived
+    ///
+    /// Call once a snapshot with the current `archive` is durable, so taken
+    /// islands the previous snapshot still needed are removed for good.
+    pub fn retain_archived(&mut self) -> Result<(), SnapshotError> {
+        let ids = self.archived_islands();
+        self.store.retain(&ids)
+    }
+
+    /// Record activity for the spaces an event involves
+    ///
+    /// Spaces already in the state that were never observed (for example,
+    /// restored from a snapshot) are treated as last active at the first
+    /// observed block.
+    pub fn observe(&mut self, event: &SpaceTopologyEvent) {
+        let block = event.meta.block_number;
+        self.current_block = self.current_block.max(block);
+        self.first_block.get_or_insert(block);
+
+        for space in event_spaces(event) {
+            let last = self.last_activity.entry(space).or_default();
+            *last = (*last).max(block);
+        }
+    }
+
+    /// Restore any archived island the event touches
+    ///
+    /// Must be called before the event is applied to the state. Returns the
+    /// number of islands restored.
+    pub fn rehydrate(
+        &mut self,
+        event: &SpaceTopologyEvent,
+        state: &mut GraphState,
+    ) -> Result<usize, SnapshotError> {
+        let mut islands: HashSet<IslandId> = event_spaces(event)
+            .iter()
+            .filter_map(|space| self.archived_spaces.get(space).copied())
+            .collect();
+
+        // A subtopic edge reaches every space announcing the topic
+        if let SpaceTopologyPayload::TrustExtended(extended) = &event.payload {
+            if let TrustExtension::Subtopic { target_topic_id } = &extended.extension {
+                if let Some(ids) = self.archived_topics.get(target_topic_id) {
+                    islands.extend(ids);
+                }
+            }
+        }
+
+        let mut restored = 0;
+        for id in islands {
+            if let Some(island) = self.store.take(id)? {
+                self.unindex(id, &island);
+                state.merge(island);
+                restored += 1;
+                self.islands_rehydrated += 1;
+            }
+        }
+
+        Ok(restored)
+    }
+
+    /// Archive every stale island in the state
+    ///
+    /// An island is a connected component that does not contain the root and
+    /// whose most recent activity is at least `stale_blocks` blocks old.
+    /// Cached transitive graphs for archived spaces are evicted. If the
+    /// store fails, the island being archived is left in the state.
+    pub fn prune(
+        &mut self,
+        state: &mut GraphState,
+        transitive: &mut TransitiveProcessor,
+    ) -> Result<PruneReport, SnapshotError> {
+        let mut report = PruneReport::default();
+
+        for island in self.stale_components(state) {
+            let id = self.next_island_id;
+            let archived = state.extract(&island);
+            if let Err(e) = self.store.put(id, &archived) {
+                state.merge(archived);
+                return Err(e);
+            }
+            self.next_island_id += 1;
+            self.index(id, &island, &archived);
+
+            for space in &island {
+                transitive.invalidate(space);
+                self.last_activity.remove(space);
+            }
+
+            report.islands += 1;
+            report.spaces += island.len();
+            self.islands_archived += 1;
+        }
+
+        Ok(report)
+    }
+
+    /// Get pruner statistics
+    pub fn stats(&self) -> PruneStats {
+        PruneStats {
+            islands_archived: self.islands_archived,
+            islands_rehydrated: self.islands_rehydrated,
+            archived_islands: self.store.len(),
+            archived_spaces: self.archived_spaces.len(),
+        }
+    }
+
+    /// IDs of the islands currently archived
+    fn archived_islands(&self) -> HashSet<IslandId> {
+        self.archived_spaces.values().copied().collect()
+    }
+
+    /// Find the connected components that are eligible for archiving
+    fn stale_components(&self, state: &GraphState) -> Vec<HashSet<SpaceId>> {
+        let adjacency = undirected_adjacency(state);
+        let mut visited: HashSet<SpaceId> = HashSet::new();
+        let mut components = Vec::new();
+
+        let mut nodes: Vec<&SpaceId> = adjacency.keys().chain(state.spaces.iter()).collect();
+        nodes.sort();
+        nodes.dedup();
+
+        for start in nodes {
+            if visited.contains(start) {
+                continue;
+            }
+
+            let mut component = HashSet::new();
+            let mut queue = VecDeque::from([*start]);
+            visited.insert(*start);
+
+            while let Some(space) = queue.pop_front() {
+                component.insert(space);
+                for neighbor in adjacency.get(&space).into_iter().flatten() {
+                    if visited.insert(*neighbor) {
+                        queue.push_back(*neighbor);
+                    }
+                }
+            }
+
+            if !component.contains(&self.root) && self.is_stale(&component) {
+                components.push(component);
+            }
+        }
+
+        components
+    }
+
+    /// Check whether every space in a component has been inactive long enough
+    fn is_stale(&self, component: &HashSet<SpaceId>) -> bool {
+        let last = component
+            .iter()
+            .filter_map(|space| self.last_activity.get(space))
+            .max()
+            .copied()
+            .unwrap_or_else(|| self.first_block.unwrap_or(0));
+
+        self.current_block.saturating_sub(last) >= self.config.stale_blocks
+    }
+
+    /// Record which spaces and topics an archived island holds
+    fn index(&mut self, id: IslandId, spaces: &HashSet<SpaceId>, island: &GraphState) {
+        for space in spaces {
+            self.archived_spaces.insert(*space, id);
+        }
+        for topic in island.topic_spaces.keys() {
+            self.archived_topics.entry(*topic).or_default().insert(id);
+        }
+    }
+
+    /// Remove an island from the archive index
+    fn unindex(&mut self, id: IslandId, island: &GraphState) {
+        let targets = island
+            .explicit_edges
+            .values()
+            .flatten()
+            .map(|(target, _)| target);
+        for space in island.spaces.iter().chain(targets) {
+            self.archived_spaces.remove(space);
+        }
+        for topic in island.topic_spaces.keys() {
+            if let Some(ids) = self.archived_topics.get_mut(topic) {
+                ids.remove(&id);
+                if ids.is_empty() {
+                    self.archived_topics.remove(topic);
+                }
+            }
+        }
+    }
+}
+
+/// Spaces directly referenced by an event
+fn event_spaces(event: &SpaceTopologyEvent) -> Vec<SpaceId> {
+    match &event.payload {
+        SpaceTopologyPayload::SpaceCreated(created) => vec![created.space_id],
+        SpaceTopologyPayload::TrustExtended(extended) => match &extended.extension {
+            TrustExtension::Verified { target_space_id }
+            | TrustExtension::Related { target_space_id } => {
+                vec![extended.source_space_id, *target_space_id]
+            }
+            TrustExtension::Subtopic { .. } => vec![extended.source_space_id],
+        },
+    }
+}
+
+/// Build an undirected adjacency list over explicit and topic edges
+fn undirected_adjacency(state: &GraphState) -> HashMap<SpaceId, Vec<SpaceId>> {
+    let mut adjacency: HashMap<SpaceId, Vec<SpaceId>> = HashMap::new();
+    let mut connect = |a: SpaceId, b: SpaceId| {
+        adjacency.entry(a).or_default().push(b);
+        adjacency.entry(b).or_default().push(a);
+    };
+
+    for (source, edges) in &state.explicit_edges {
+        for (target, _) in edges {
+            connect(*source, *target);
+        }
+    }
+
+    for (source, topics) in &state.topic_edges {
+        for topic in topics {
+            for member in state.topic_spaces.get(topic).into_iter().flatten() {
+                connect(*source, *member);
+            }
+        }
+    }
+
+    adjacency
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{BlockMetadata, SpaceCreated, SpaceType, TrustExtended};
+    use crate::graph::CanonicalProcessor;
+
+    fn make_id(n: u8) -> [u8; 16] {
+        let mut id = [0u8; 16];
+        id[15] = n;
+        id
+    }
+
+    fn make_block_meta(block: u64) -> BlockMetadata {
+        BlockMetadata {
+            block_number: block,
+            block_timestamp: block * 12,
+            tx_hash: format!("0x{:064x}", block),
+            cursor: format!("cursor_{}", block),
+        }
+    }
+
+    fn space_created(space: u8, topic: u8, block: u64) -> SpaceTopologyEvent {
+        SpaceTopologyEvent {
+            meta: make_block_meta(block),
+            payload: SpaceTopologyPayload::SpaceCreated(SpaceCreated {
+                space_id: make_id(space),
+                topic_id: make_id(topic),
+                space_type: SpaceType::Dao {
+                    initial_editors: vec![],
+                    initial_members: vec![],
+                },
+            }),
+        }
+    }
+
+    fn trust(source: u8, extension: TrustExtension, block: u64) -> SpaceTopologyEvent {
+        SpaceTopologyEvent {
+            meta: make_block_meta(block),
+            payload: SpaceTopologyPayload::TrustExtended(TrustExtended {
+                source_space_id: make_id(source),
+                extension,
+            }),
+        }
+    }
+
+    fn verified(source: u8, target: u8, block: u64) -> SpaceTopologyEvent {
+        trust(
+            source,
+            TrustExtension::Verified {
+                target_space_id: make_id(target),
+            },
+            block,
+        )
+    }
+
+    fn subtopic(source: u8, topic: u8, block: u64) -> SpaceTopologyEvent {
+        trust(
+            source,
+            TrustExtension::Subtopic {
+                target_topic_id: make_id(topic),
+            },
+            block,
+        )
+    }
+
+    struct Harness<S: ColdStore = MemoryColdStore> {
+        state: GraphState,
+        transitive: TransitiveProcessor,
+        pruner: IslandPruner<S>,
+    }
+
+    impl Harness {
+        fn new(stale_blocks: u64) -> Self {
+            Self::with_store(stale_blocks, MemoryColdStore::new())
+        }
+    }
+
+    impl<S: ColdStore> Harness<S> {
+        fn with_store(stale_blocks: u64, store: S) -> Self {
+            Self {
+                state: GraphState::new(),
+                transitive: TransitiveProcessor::new(),
+                pruner: IslandPruner::with_store(
+                    make_id(1),
+                    PruneConfig::default().with_stale_blocks(stale_blocks),
+                    store,
+                ),
+            }
+        }
+
+        fn apply(&mut self, event: SpaceTopologyEvent) {
+            self.pruner.rehydrate(&event, &mut self.state).unwrap();
+            self.pruner.observe(&event);
+            self.transitive.handle_event(&event, &self.state);
+            self.state.apply_event(&event);
+        }
+
+        fn prune(&mut self) -> PruneReport {
+            self.pruner
+                .prune(&mut self.state, &mut self.transitive)
+                .unwrap()
+        }
+    }
+
+    /// Root (1) -> A (2); island X (10) -> Y (11) created at block 1
+    fn harness_with_island(stale_blocks: u64) -> Harness {
+        add_island(Harness::new(stale_blocks))
+    }
+
+    fn add_island<S: ColdStore>(mut harness: Harness<S>) -> Harness<S> {
+        harness.apply(space_created(1, 101, 1));
+        harness.apply(space_created(2, 102, 1));
+        harness.apply(verified(1, 2, 1));
+        harness.apply(space_created(10, 110, 1));
+        harness.apply(space_created(11, 111, 1));
+        harness.apply(verified(10, 11, 1));
+        harness
+    }
+
+    #[test]
+    fn test_stale_island_archived() {
+        let mut harness = harness_with_island(100);
+        // Later activity in the canonical component only
+        harness.apply(space_created(3, 103, 200));
+        harness.apply(verified(2, 3, 200));
+
+        let report = harness.prune();
+
+        assert_eq!(
+            report,
+            PruneReport {
+                islands: 1,
+                spaces: 2
+            }
+        );
+        assert_eq!(harness.state.space_count(), 3);
+        assert!(harness.pruner.is_archived(&make_id(10)));
+        assert!(harness.pruner.is_archived(&make_id(11)));
+        assert_eq!(harness.pruner.stats().archived_spaces, 2);
+        assert_eq!(harness.pruner.store().len(), 1);
+    }
+
+    #[test]
+    fn test_recent_island_kept() {
+        let mut harness = harness_with_island(100);
+        harness.apply(space_created(3, 103, 50));
+
+        assert_eq!(harness.prune(), PruneReport::default());
+        assert_eq!(harness.state.space_count(), 5);
+    }
+
+    #[test]
+    fn test_root_component_never_archived() {
+        let mut harness = Harness::new(10);
+        harness.apply(space_created(1, 101, 1));
+        harness.apply(space_created(2, 102, 1));
+        harness.apply(verified(1, 2, 1));
+        harness.apply(space_created(3, 103, 1_000));
+
+        let report = harness.prune();
+
+        // Only the unconnected space 3 is an island, and it is not stale
+        assert_eq!(report.islands, 0);
+        assert!(harness.state.contains_space(&make_id(1)));
+        assert!(harness.state.contains_space(&make_id(2)));
+    }
+
+    #[test]
+    fn test_trust_into_archived_island_rehydrates() {
+        let mut harness = harness_with_island(100);
+        harness.apply(space_created(3, 103, 200));
+        harness.prune();
+
+        // The root now trusts X, pulling the island back into the canonical graph
+        harness.apply(verified(1, 10, 201));
+
+        assert!(!harness.pruner.is_archived(&make_id(10)));
+        assert!(harness.state.contains_space(&make_id(11)));
+        assert_eq!(harness.pruner.stats().islands_rehydrated, 1);
+        assert!(harness.pruner.store().is_empty());
+
+        let mut canonical = CanonicalProcessor::new(make_id(1));
+        let graph = canonical
+            .compute(&harness.state, &mut harness.transitive)
+            .unwrap();
+        assert!(graph.contains(&make_id(11)));
+    }
+
+    #[test]
+    fn test_subtopic_edge_rehydrates_announcing_island() {
+        let mut harness = harness_with_island(100);
+        harness.apply(space_created(3, 103, 200));
+        harness.prune();
+
+        // Y announces topic 111
+        harness.apply(subtopic(2, 111, 201));
+
+        assert!(harness.state.contains_space(&make_id(11)));
+        assert!(harness.state.contains_space(&make_id(10)));
+        assert_eq!(harness.pruner.stats().archived_spaces, 0);
+    }
+
+    #[test]
+    fn test_canonical_graph_unchanged_by_pruning() {
+        let mut harness = harness_with_island(100);
+        harness.apply(space_created(3, 103, 200));
+
+        let mut canonical = CanonicalProcessor::new(make_id(1));
+        let before = canonical
+            .compute(&harness.state, &mut harness.transitive)
+            .unwrap()
+            .flat
+            .clone();
+
+        harness.prune();
+
+        let mut canonical = CanonicalProcessor::new(make_id(1));
+        let mut transitive = TransitiveProcessor::new();
+        let after = canonical
+            .compute(&harness.state, &mut transitive)
+            .unwrap()
+            .flat
+            .clone();
+        assert_eq!(before, after);
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("atlas-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_disk_store_round_trip() {
+        let dir = temp_dir("cold-store");
+        let harness = harness_with_island(100);
+
+        let mut store = DiskColdStore::open(&dir).unwrap();
+        store.put(7, &harness.state).unwrap();
+        assert_eq!(DiskColdStore::open(&dir).unwrap().len(), 1);
+
+        let island = store.take(7).unwrap().unwrap();
+        assert_eq!(island.spaces, harness.state.spaces);
+        assert_eq!(island.explicit_edges, harness.state.explicit_edges);
+        assert!(store.is_empty());
+        assert!(store.take(7).unwrap().is_none());
+
+        // A taken island stays on disk until it is no longer retained
+        assert!(dir.join("island-7.json").exists());
+        store.retain(&HashSet::new()).unwrap();
+        assert!(!dir.join("island-7.json").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_archive_restored_after_restart() {
+        let dir = temp_dir("cold-restart");
+        let mut harness = add_island(Harness::with_store(100, DiskColdStore::open(&dir).unwrap()));
+        harness.apply(space_created(3, 103, 200));
+        harness.prune();
+        let archive = harness.pruner.archive();
+        assert_eq!(archive.next_island_id, 1);
+        assert_eq!(archive.spaces.len(), 2);
+
+        // Restart from the live state and the archive index
+        let mut restarted = Harness::with_store(100, DiskColdStore::open(&dir).unwrap());
+        restarted.state = harness.state.clone();
+        restarted.pruner.restore_archive(&archive).unwrap();
+        assert!(restarted.pruner.is_archived(&make_id(10)));
+
+        restarted.apply(verified(1, 10, 201));
+        assert!(restarted.state.contains_space(&make_id(11)));
+        assert!(restarted.pruner.store().is_empty());
+
+        // The island file is kept for the old snapshot until the next one is saved
+        assert!(dir.join("island-0.json").exists());
+        restarted.pruner.retain_archived().unwrap();
+        assert!(!dir.join("island-0.json").exists());
+
+        // The old index now refers to an island the store no longer has
+        let mut stale = Harness::with_store(100, DiskColdStore::open(&dir).unwrap());
+        assert!(stale.pruner.restore_archive(&archive).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -4,15 +4,46 @@
 //! updated by processing blockchain events.
 
 use crate::events::{
-    SpaceCreated, SpaceId, SpaceTopologyEvent, SpaceTopologyPayload, TopicId, TrustExtended,
-    TrustExtension,
+    BlockMetadata, SpaceCreated, SpaceId, SpaceTopologyEvent, SpaceTopologyPayload, TopicId,
+    TrustExtended, TrustExtension,
 };
 use std::collections::{HashMap, HashSet};
 
 use super::EdgeType;
 
+/// Provenance of a trust edge
+///
+/// Records which space created the edge and the block it was created in.
+/// Only the first occurrence of an edge is recorded; re-extending the same
+/// trust does not overwrite the original provenance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeMetadata {
+    /// The space that extended trust
+    pub created_by: SpaceId,
+
+    /// Block in which the edge was created
+    pub block_number: u64,
+
+    /// Timestamp of that block (unix seconds)
+    pub block_timestamp: u64,
+
+    /// Transaction that created the edge
+    pub tx_hash: String,
+}
+
+impl EdgeMetadata {
+    fn new(created_by: SpaceId, meta: &BlockMetadata) -> Self {
+        Self {
+            created_by,
+            block_number: meta.block_number,
+            block_timestamp: meta.block_timestamp,
+            tx_hash: meta.tx_hash.clone(),
+        }
+    }
+}
+
 /// In-memory state of the topology graph
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct GraphState {
     /// All known spaces
     pub spaces: HashSet<SpaceId>,
@@ -32,6 +63,12 @@ pub struct GraphState {
     /// Reverse topic edges: topic -> spaces that have edges TO this topic
     /// Used for O(1) lookup of which spaces are affected when a topic changes
     pub topic_edge_sources: HashMap<TopicId, HashSet<SpaceId>>,
+
+    /// Provenance of explicit edges: (source, target, edge_type) -> metadata
+    pub explicit_edge_metadata: HashMap<(SpaceId, SpaceId, EdgeType), EdgeMetadata>,
+
+    /// Provenance of topic edges: (source, topic_id) -> metadata
+    pub topic_edge_metadata: HashMap<(SpaceId, TopicId), EdgeMetadata>,
 }
 
 impl GraphState {
@@ -47,7 +84,7 @@ impl GraphState {
                 self.apply_space_created(created);
             }
             SpaceTopologyPayload::TrustExtended(extended) => {
-                self.apply_trust_extended(extended);
+                self.apply_trust_extended(extended, &event.meta);
             }
         }
     }
@@ -68,21 +105,15 @@ impl GraphState {
     }
 
     /// Apply a TrustExtended event
-    fn apply_trust_extended(&mut self, event: &TrustExtended) {
+    fn apply_trust_extended(&mut self, event: &TrustExtended, meta: &BlockMetadata) {
         let source = event.source_space_id;
 
         match &event.extension {
             TrustExtension::Verified { target_space_id } => {
-                self.explicit_edges
-                    .entry(source)
-                    .or_default()
-                    .push((*target_space_id, EdgeType::Verified));
+                self.add_explicit_edge(source, *target_space_id, EdgeType::Verified, meta);
             }
             TrustExtension::Related { target_space_id } => {
-                self.explicit_edges
-                    .entry(source)
-                    .or_default()
-                    .push((*target_space_id, EdgeType::Related));
+                self.add_explicit_edge(source, *target_space_id, EdgeType::Related, meta);
             }
             TrustExtension::Subtopic { target_topic_id } => {
                 self.topic_edges
@@ -95,10 +126,131 @@ impl GraphState {
                     .entry(*target_topic_id)
                     .or_default()
                     .insert(source);
+
+                self.topic_edge_metadata
+                    .entry((source, *target_topic_id))
+                    .or_insert_with(|| EdgeMetadata::new(source, meta));
             }
         }
     }
 
+    /// Add an explicit edge and record its provenance
+    fn add_explicit_edge(
+        &mut self,
+        source: SpaceId,
+        target: SpaceId,
+        edge_type: EdgeType,
+        meta: &BlockMetadata,
+    ) {
+        self.explicit_edges
+            .entry(source)
+            .or_default()
+            .push((target, edge_type));
+
+        self.explicit_edge_metadata
+            .entry((source, target, edge_type))
+            .or_insert_with(|| EdgeMetadata::new(source, meta));
+    }
+
+    /// Move a set of spaces and their outgoing edges into a new graph state
+    ///
+    /// Removes the spaces, their topic announcements, and all explicit and
+    /// topic edges they are the source of, along with edge provenance. Edges
+    /// from other spaces into the set are left in place, so callers should
+    /// pass a set that is closed under incoming edges.
+    pub fn extract(&mut self, spaces: &HashSet<SpaceId>) -> GraphState {
+        let mut extracted = GraphState::new();
+
+        for space in spaces {
+            if self.spaces.remove(space) {
+                extracted.spaces.insert(*space);
+            }
+
+            if let Some(topic) = self.space_topics.remove(space) {
+                extracted.space_topics.insert(*space, topic);
+                if let Some(members) = self.topic_spaces.get_mut(&topic) {
+                    members.remove(space);
+                    if members.is_empty() {
+                        self.topic_spaces.remove(&topic);
+                    }
+                }
+                extracted
+                    .topic_spaces
+                    .entry(topic)
+                    .or_default()
+                    .insert(*space);
+            }
+
+            if let Some(edges) = self.explicit_edges.remove(space) {
+                for (target, edge_type) in &edges {
+                    if let Some(meta) = self
+                        .explicit_edge_metadata
+                        .remove(&(*space, *target, *edge_type))
+                    {
+                        extracted
+                            .explicit_edge_metadata
+                            .insert((*space, *target, *edge_type), meta);
+                    }
+                }
+                extracted.explicit_edges.insert(*space, edges);
+            }
+
+            if let Some(topics) = self.topic_edges.remove(space) {
+                for topic in &topics {
+                    if let Some(sources) = self.topic_edge_sources.get_mut(topic) {
+                        sources.remove(space);
+                        if sources.is_empty() {
+                            self.topic_edge_sources.remove(topic);
+                        }
+                    }
+                    extracted
+                        .topic_edge_sources
+                        .entry(*topic)
+                        .or_default()
+                        .insert(*space);
+
+                    if let Some(meta) = self.topic_edge_metadata.remove(&(*space, *topic)) {
+                        extracted.topic_edge_metadata.insert((*space, *topic), meta);
+                    }
+                }
+                extracted.topic_edges.insert(*space, topics);
+            }
+        }
+
+        extracted
+    }
+
+    /// Merge another graph state into this one
+    ///
+    /// The inverse of `extract`. Existing edge provenance is kept when both
+    /// states record the same edge.
+    pub fn merge(&mut self, other: GraphState) {
+        self.spaces.extend(other.spaces);
+        self.space_topics.extend(other.space_topics);
+
+        for (topic, members) in other.topic_spaces {
+            self.topic_spaces.entry(topic).or_default().extend(members);
+        }
+        for (source, edges) in other.explicit_edges {
+            self.explicit_edges.entry(source).or_default().extend(edges);
+        }
+        for (source, topics) in other.topic_edges {
+            self.topic_edges.entry(source).or_default().extend(topics);
+        }
+        for (topic, sources) in other.topic_edge_sources {
+            self.topic_edge_sources
+                .entry(topic)
+                .or_default()
+                .extend(sources);
+        }
+        for (key, meta) in other.explicit_edge_metadata {
+            self.explicit_edge_metadata.entry(key).or_insert(meta);
+        }
+        for (key, meta) in other.topic_edge_metadata {
+            self.topic_edge_metadata.entry(key).or_insert(meta);
+        }
+    }
+
     /// Check if a space exists in the graph
     pub fn contains_space(&self, space_id: &SpaceId) -> bool {
         self.spaces.contains(space_id)
@@ -129,6 +281,26 @@ impl GraphState {
         self.topic_edge_sources.get(topic_id)
     }
 
+    /// Get the provenance of an explicit edge
+    pub fn get_explicit_edge_metadata(
+        &self,
+        source: &SpaceId,
+        target: &SpaceId,
+        edge_type: EdgeType,
+    ) -> Option<&EdgeMetadata> {
+        self.explicit_edge_metadata
+            .get(&(*source, *target, edge_type))
+    }
+
+    /// Get the provenance of a topic edge
+    pub fn get_topic_edge_metadata(
+        &self,
+        source: &SpaceId,
+        topic_id: &TopicId,
+    ) -> Option<&EdgeMetadata> {
+        self.topic_edge_metadata.get(&(*source, *topic_id))
+    }
+
     /// Get total number of spaces
     pub fn space_count(&self) -> usize {
         self.spaces.len()
@@ -260,6 +432,64 @@ mod tests {
         assert!(topic_edges.contains(&topic2));
     }
 
+    #[test]
+    fn test_explicit_edge_metadata_retained() {
+        let mut state = GraphState::new();
+        let space1 = make_space_id(1);
+        let space2 = make_space_id(2);
+
+        state.apply_event(&make_space_created_event(space1, make_topic_id(1)));
+        state.apply_event(&make_space_created_event(space2, make_topic_id(2)));
+        state.apply_event(&make_verified_event(space1, space2));
+
+        let meta = state
+            .get_explicit_edge_metadata(&space1, &space2, EdgeType::Verified)
+            .unwrap();
+        assert_eq!(meta.created_by, space1);
+        assert_eq!(meta.block_number, 2);
+        assert_eq!(meta.block_timestamp, 24);
+        assert_eq!(meta.tx_hash, format!("0x{:064x}", 2));
+
+        // Same pair with a different edge type has no provenance
+        assert!(state
+            .get_explicit_edge_metadata(&space1, &space2, EdgeType::Related)
+            .is_none());
+    }
+
+    #[test]
+    fn test_edge_metadata_keeps_first_occurrence() {
+        let mut state = GraphState::new();
+        let space1 = make_space_id(1);
+        let space2 = make_space_id(2);
+
+        state.apply_event(&make_verified_event(space1, space2));
+
+        let mut repeated = make_verified_event(space1, space2);
+        repeated.meta = make_block_meta(10);
+        state.apply_event(&repeated);
+
+        let meta = state
+            .get_explicit_edge_metadata(&space1, &space2, EdgeType::Verified)
+            .unwrap();
+        assert_eq!(meta.block_number, 2);
+    }
+
+    #[test]
+    fn test_topic_edge_metadata_retained() {
+        let mut state = GraphState::new();
+        let space1 = make_space_id(1);
+        let topic2 = make_topic_id(2);
+
+        state.apply_event(&make_subtopic_event(space1, topic2));
+
+        let meta = state.get_topic_edge_metadata(&space1, &topic2).unwrap();
+        assert_eq!(meta.created_by, space1);
+        assert_eq!(meta.block_number, 3);
+        assert!(state
+            .get_topic_edge_metadata(&make_space_id(2), &topic2)
+            .is_none());
+    }
+
     #[test]
     fn test_topic_members() {
         let mut state = GraphState::new();
@@ -277,4 +507,50 @@ mod tests {
         assert!(members.contains(&space1));
         assert!(members.contains(&space2));
     }
+
+    #[test]
+    fn test_extract_and_merge_round_trip() {
+        let mut state = GraphState::new();
+        let space1 = make_space_id(1);
+        let space2 = make_space_id(2);
+        let space3 = make_space_id(3);
+        let topic2 = make_topic_id(2);
+
+        state.apply_event(&make_space_created_event(space1, make_topic_id(1)));
+        state.apply_event(&make_space_created_event(space2, topic2));
+        state.apply_event(&make_space_created_event(space3, make_topic_id(3)));
+        state.apply_event(&make_verified_event(space2, space3));
+        state.apply_event(&make_subtopic_event(space3, topic2));
+
+        let island: HashSet<SpaceId> = [space2, space3].into_iter().collect();
+        let extracted = state.extract(&island);
+
+        assert_eq!(state.space_count(), 1);
+        assert_eq!(state.explicit_edge_count(), 0);
+        assert_eq!(state.topic_edge_count(), 0);
+        assert!(state.get_topic_members(&topic2).is_none());
+        assert!(state.get_topic_edge_sources(&topic2).is_none());
+        assert!(state.explicit_edge_metadata.is_empty());
+
+        assert_eq!(extracted.space_count(), 2);
+        assert_eq!(extracted.explicit_edge_count(), 1);
+        assert_eq!(extracted.topic_edge_count(), 1);
+
+        state.merge(extracted);
+
+        assert_eq!(state.space_count(), 3);
+        assert_eq!(state.get_explicit_edges(&space2).unwrap().len(), 1);
+        assert!(state.get_topic_members(&topic2).unwrap().contains(&space2));
+        assert!(state
+            .get_topic_edge_sources(&topic2)
+            .unwrap()
+            .contains(&space3));
+        assert_eq!(
+            state
+                .get_explicit_edge_metadata(&space2, &space3, EdgeType::Verified)
+                .unwrap()
+                .block_number,
+            2
+        );
+    }
 }
@@ -346,6 +346,11 @@ impl TransitiveProcessor {
         TransitiveGraph::new(root, tree, visited)
     }
 
+    /// Evict cached graphs for a space and every graph that contains it
+    pub fn invalidate(&mut self, space: &SpaceId) {
+        self.cache.invalidate(space);
+    }
+
     /// Get cache statistics
     pub fn cache_stats(&self) -> CacheStats {
         self.cache.stats()
@@ -0,0 +1,53 @@
+//! Trust anomaly emitter
+//!
+//! Emits `TrustRateAnomaly` alerts to Kafka when the anomaly detector flags
+//! a space creating trust edges at an abnormal rate.
+
+use crate::anomaly::TrustRateAnomaly;
+use crate::kafka::{AtlasProducer, ProducerError};
+use hermes_schema::pb::blockchain_metadata::BlockchainMetadata as ProtoBlockchainMetadata;
+use hermes_schema::pb::topology::TrustRateAnomaly as ProtoTrustRateAnomaly;
+use prost::Message;
+
+/// Emits trust rate anomaly alerts to Kafka
+pub struct TrustAnomalyEmitter {
+    producer: AtlasProducer,
+}
+
+impl TrustAnomalyEmitter {
+    /// Create a new emitter with the given producer
+    pub fn new(producer: AtlasProducer) -> Self {
+        Self { producer }
+    }
+
+    /// Emit an anomaly alert to Kafka, keyed by the offending space
+    pub fn emit(&self, anomaly: &TrustRateAnomaly) -> Result<(), ProducerError> {
+        let alert = ProtoTrustRateAnomaly {
+            space_id: anomaly.space_id.to_vec(),
+            edge_count: anomaly.edge_count as u64,
+            window_seconds: anomaly.window_seconds,
+            threshold: anomaly.threshold as u64,
+            meta: Some(ProtoBlockchainMetadata {
+                created_at: anomaly.meta.block_timestamp,
+                created_by: Vec::new(),
+                block_number: anomaly.meta.block_number,
+                cursor: anomaly.meta.cursor.clone(),
+            }),
+        };
+
+        let mut payload = Vec::with_capacity(alert.encoded_len());
+        alert
+            .encode(&mut payload)
+            .expect("Vec<u8> provides sufficient buffer capacity");
+
+        self.producer.send_and_flush(&anomaly.space_id, &payload)
+    }
+}
+
+impl std::fmt::Debug for TrustAnomalyEmitter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TrustAnomalyEmitter")
+            .field("topic", &self.producer.topic())
+            .finish_non_exhaustive()
+    }
+}
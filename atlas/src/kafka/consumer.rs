@@ -0,0 +1,847 @@
+//! Kafka consumer for Atlas
+//!
+//! Subscribes to the space topology topics published by hermes-processor and
+//! decodes each message into a `SpaceTopologyEvent`.
+
+use crate::decode::{decode_space_created, decode_trust_extended, DecodeError};
+use crate::events::SpaceTopologyEvent;
+use crate::kafka::DeadLetter;
+use hermes_schema::version::{parse_schema_version, SCHEMA_VERSION_HEADER};
+use rdkafka::config::ClientConfig;
+use rdkafka::consumer::{BaseConsumer, CommitMode, Consumer, ConsumerContext, Rebalance};
+use rdkafka::error::KafkaError;
+use rdkafka::message::Headers;
+use rdkafka::{ClientContext, Message, Offset, TopicPartitionList};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Default topic carrying `HermesCreateSpace` messages
+pub const SPACE_CREATIONS_TOPIC: &str = "space.creations";
+
+/// Default topic carrying `HermesSpaceTrustExtension` messages
+pub const TRUST_EXTENSIONS_TOPIC: &str = "space.trust.extensions";
+
+/// Error types for consumer operations
+#[derive(Debug)]
+pub enum ConsumerError {
+    /// Failed to create the Kafka consumer
+    Creation(KafkaError),
+    /// Failed to subscribe to the topology topics
+    Subscribe(KafkaError),
+    /// Failed to receive a message
+    Receive(KafkaError),
+    /// Failed to seek a partition to its configured start offset
+    Seek(KafkaError),
+    /// Failed to commit processed offsets
+    Commit(KafkaError),
+    /// A message was written with a schema version this consumer cannot decode
+    ///
+    /// Carries the raw message so the caller can dead-letter it.
+    UnsupportedVersion {
+        version: Option<u32>,
+        message: Box<DeadLetter>,
+    },
+    /// A message could not be decoded into a topology event
+    Decode {
+        topic: String,
+        partition: i32,
+        offset: i64,
+        source: DecodeError,
+    },
+}
+
+impl std::fmt::Display for ConsumerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConsumerError::Creation(e) => write!(f, "failed to create consumer: {}", e),
+            ConsumerError::Subscribe(e) => write!(f, "failed to subscribe: {}", e),
+            ConsumerError::Receive(e) => write!(f, "failed to receive message: {}", e),
+            ConsumerError::Seek(e) => write!(f, "failed to seek to start offset: {}", e),
+            ConsumerError::Commit(e) => write!(f, "failed to commit offsets: {}", e),
+            ConsumerError::UnsupportedVersion { version, message } => match version {
+                Some(version) => write!(
+                    f,
+                    "unsupported schema version {} at {}[{}]@{}",
+                    version, message.topic, message.partition, message.offset
+                ),
+                None => write!(
+                    f,
+                    "invalid schema version header at {}[{}]@{}",
+                    message.topic, message.partition, message.offset
+                ),
+            },
+            ConsumerError::Decode {
+                topic,
+                partition,
+                offset,
+                source,
+            } => write!(
+                f,
+                "failed to decode message at {}[{}]@{}: {}",
+                topic, partition, offset, source
+            ),
+        }
+    }
+}
+
+impl ConsumerError {
+    /// Get the raw message to dead-letter, if this error carries one
+    pub fn dead_letter(&self) -> Option<&DeadLetter> {
+        match self {
+            ConsumerError::UnsupportedVersion { message, .. } => Some(message),
+            _ => None,
+        }
+    }
+}
+
+impl std::error::Error for ConsumerError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConsumerError::Creation(e) => Some(e),
+            ConsumerError::Subscribe(e) => Some(e),
+            ConsumerError::Receive(e) => Some(e),
+            ConsumerError::Seek(e) => Some(e),
+            ConsumerError::Commit(e) => Some(e),
+            ConsumerError::UnsupportedVersion { .. } => None,
+            ConsumerError::Decode { source, .. } => Some(source),
+        }
+    }
+}
+
+/// Names of the topics Atlas consumes topology events from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsumerTopics {
+    /// Topic carrying `HermesCreateSpace` messages
+    pub creations: String,
+    /// Topic carrying `HermesSpaceTrustExtension` messages
+    pub trust_extensions: String,
+}
+
+impl Default for ConsumerTopics {
+    fn default() -> Self {
+        Self {
+            creations: SPACE_CREATIONS_TOPIC.to_string(),
+            trust_extensions: TRUST_EXTENSIONS_TOPIC.to_string(),
+        }
+    }
+}
+
+impl ConsumerTopics {
+    /// Decode a message payload according to the topic it was read from
+    ///
+    /// Returns `Ok(None)` for topics Atlas does not consume.
+    pub fn decode(
+        &self,
+        topic: &str,
+        payload: &[u8],
+    ) -> Result<Option<SpaceTopologyEvent>, DecodeError> {
+        if topic == self.creations {
+            decode_space_created(payload).map(Some)
+        } else if topic == self.trust_extensions {
+            decode_trust_extended(payload).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Decode a message payload with the decoder for its schema version
+    ///
+    /// Returns `None` if this consumer does not know how to decode `version`.
+    pub fn decode_versioned(
+        &self,
+        version: u32,
+        topic: &str,
+        payload: &[u8],
+    ) -> Option<Result<Option<SpaceTopologyEvent>, DecodeError>> {
+        match version {
+            1 => Some(self.decode(topic, payload)),
+            _ => None,
+        }
+    }
+}
+
+/// Where a consumer group without committed offsets starts reading
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OffsetReset {
+    /// Start from the oldest retained message
+    #[default]
+    Earliest,
+    /// Start from new messages only
+    Latest,
+}
+
+impl OffsetReset {
+    /// Value for rdkafka's `auto.offset.reset` setting
+    fn as_config_value(self) -> &'static str {
+        match self {
+            OffsetReset::Earliest => "earliest",
+            OffsetReset::Latest => "latest",
+        }
+    }
+}
+
+impl FromStr for OffsetReset {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "earliest" => Ok(OffsetReset::Earliest),
+            "latest" => Ok(OffsetReset::Latest),
+            other => Err(format!("unknown offset reset: {}", other)),
+        }
+    }
+}
+
+/// Explicit start offset for one partition, parsed from `topic:partition:offset`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionOffset {
+    pub topic: String,
+    pub partition: i32,
+    pub offset: i64,
+}
+
+impl FromStr for PartitionOffset {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("expected TOPIC:PARTITION:OFFSET, got {}", value);
+        let mut parts = value.rsplitn(3, ':');
+        let offset = parts.next().ok_or_else(invalid)?;
+        let partition = parts.next().ok_or_else(invalid)?;
+        let topic = parts.next().filter(|t| !t.is_empty()).ok_or_else(invalid)?;
+
+        let offset: i64 = offset.parse().map_err(|_| invalid())?;
+        if offset < 0 {
+            return Err(format!("offset must not be negative, got {}", value));
+        }
+
+        Ok(PartitionOffset {
+            topic: topic.to_string(),
+            partition: partition.parse().map_err(|_| invalid())?,
+            offset,
+        })
+    }
+}
+
+/// Where the consumer starts reading the topology topics
+///
+/// Committed group offsets are used where they exist; `offset_reset` applies
+/// to partitions without one. Explicit partition offsets override both, and
+/// `start_block` skips older events wherever reading starts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsumerStart {
+    /// Start position for partitions without a committed offset
+    pub offset_reset: OffsetReset,
+    /// Partitions to seek to an explicit offset when first assigned
+    pub partition_offsets: Vec<PartitionOffset>,
+    /// Skip events from blocks before this one
+    pub start_block: Option<u64>,
+}
+
+impl ConsumerStart {
+    /// Set the start position for partitions without a committed offset
+    pub fn with_offset_reset(mut self, offset_reset: OffsetReset) -> Self {
+        self.offset_reset = offset_reset;
+        self
+    }
+
+    /// Start a partition at an explicit offset
+    pub fn with_partition_offset(mut self, partition_offset: PartitionOffset) -> Self {
+        self.partition_offsets.push(partition_offset);
+        self
+    }
+
+    /// Skip events from blocks before `block_number`
+    pub fn with_start_block(mut self, block_number: u64) -> Self {
+        self.start_block = Some(block_number);
+        self
+    }
+}
+
+/// Consumer context recording partitions as the group assigns them
+#[derive(Default)]
+struct AtlasConsumerContext {
+    /// Partitions assigned since the consumer last applied start offsets
+    assigned: Mutex<Vec<(String, i32)>>,
+}
+
+impl ClientContext for AtlasConsumerContext {}
+
+impl ConsumerContext for AtlasConsumerContext {
+    fn post_rebalance(&self, rebalance: &Rebalance<'_>) {
+        if let Rebalance::Assign(partitions) = rebalance {
+            let mut assigned = self.assigned.lock().unwrap_or_else(|e| e.into_inner());
+            assigned.extend(
+                partitions
+                    .elements()
+                    .iter()
+                    .map(|p| (p.topic().to_string(), p.partition())),
+            );
+        }
+    }
+}
+
+/// Offsets of messages the caller has finished with, per topic and partition
+///
+/// The event most recently returned by `poll` is held back until the caller
+/// marks it processed, so a commit never covers an event that has not been
+/// applied yet.
+#[derive(Debug, Default)]
+struct OffsetTracker {
+    /// Next offset to read per partition, covering every processed message
+    processed: HashMap<(String, i32), i64>,
+    /// Position of the event returned to the caller but not yet processed
+    delivered: Option<(String, i32, i64)>,
+}
+
+impl OffsetTracker {
+    /// Record a message that needs no further handling
+    fn process(&mut self, topic: &str, partition: i32, offset: i64) {
+        self.processed
+            .insert((topic.to_string(), partition), offset + 1);
+    }
+
+    /// Record a message returned to the caller as an event
+    fn deliver(&mut self, topic: &str, partition: i32, offset: i64) {
+        self.delivered = Some((topic.to_string(), partition, offset));
+    }
+
+    /// Mark the last delivered event as processed
+    fn mark_processed(&mut self) {
+        if let Some((topic, partition, offset)) = self.delivered.take() {
+            self.process(&topic, partition, offset);
+        }
+    }
+
+    /// Take the offsets processed since the last commit
+    fn take_processed(&mut self) -> Vec<(String, i32, i64)> {
+        let mut offsets: Vec<_> = self
+            .processed
+            .drain()
+            .map(|((topic, partition), offset)| (topic, partition, offset))
+            .collect();
+        offsets.sort();
+        offsets
+    }
+}
+
+/// Kafka consumer for space topology events
+///
+/// Wraps rdkafka's BaseConsumer. Offsets are not committed automatically:
+/// the caller commits them with `commit` once the events up to that point
+/// are durably reflected in its state, e.g. after saving a state snapshot.
+pub struct AtlasConsumer {
+    consumer: BaseConsumer<AtlasConsumerContext>,
+    topics: ConsumerTopics,
+    offsets: OffsetTracker,
+    /// Explicit start offsets not yet applied, by topic and partition
+    pending_seeks: HashMap<(String, i32), i64>,
+    start_block: Option<u64>,
+}
+
+impl AtlasConsumer {
+    /// Create a consumer in the given group and subscribe to the topology topics
+    ///
+    /// # Arguments
+    ///
+    /// * `broker` - Kafka bootstrap server address (e.g., "localhost:9092")
+    /// * `group_id` - Consumer group ID (e.g., "atlas")
+    /// * `topics` - Topics to consume creations and trust extensions from
+    /// * `start` - Where to start reading
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let start = ConsumerStart::default().with_offset_reset(OffsetReset::Latest);
+    /// let consumer = AtlasConsumer::new("localhost:9092", "atlas", ConsumerTopics::default(), start)?;
+    /// ```
+    pub fn new(
+        broker: &str,
+        group_id: &str,
+        topics: ConsumerTopics,
+        start: ConsumerStart,
+    ) -> Result<Self, ConsumerError> {
+        let mut config = ClientConfig::new();
+
+        config
+            .set("bootstrap.servers", broker)
+            .set("group.id", group_id)
+            .set("client.id", "atlas-consumer")
+            .set("enable.auto.commit", "false")
+            .set("auto.offset.reset", start.offset_reset.as_config_value());
+
+        super::producer::apply_security_config(&mut config);
+
+        let consumer: BaseConsumer<AtlasConsumerContext> = config
+            .create_with_context(AtlasConsumerContext::default())
+            .map_err(ConsumerError::Creation)?;
+        consumer
+            .subscribe(&[&topics.creations, &topics.trust_extensions])
+            .map_err(ConsumerError::Subscribe)?;
+
+        let pending_seeks = start
+            .partition_offsets
+            .into_iter()
+            .map(|p| ((p.topic, p.partition), p.offset))
+            .collect();
+
+        Ok(Self {
+            consumer,
+            topics,
+            offsets: OffsetTracker::default(),
+            pending_seeks,
+            start_block: start.start_block,
+        })
+    }
+
+    /// Poll for the next topology event
+    ///
+    /// Returns `Ok(None)` if no message arrived within `timeout`, or if the
+    /// message was skipped: it carried no payload or its block is before the
+    /// start block.
+    /// Messages with an unknown schema version are reported as
+    /// `ConsumerError::UnsupportedVersion`. Like events, they are held back
+    /// from the next commit until the caller calls `mark_processed` (e.g.
+    /// after dead-lettering them) or `redeliver`.
+    pub fn poll(&mut self, timeout: Duration) -> Result<Option<SpaceTopologyEvent>, ConsumerError> {
+        let message = match self.consumer.poll(timeout) {
+            None => {
+                // A rebalance ends the poll without a message, so seek newly
+                // assigned partitions before anything is read from them
+                self.seek_assigned(timeout)?;
+                return Ok(None);
+            }
+            Some(result) => result.map_err(ConsumerError::Receive)?,
+        };
+
+        let result = decode_message(&self.topics, self.start_block, &message);
+
+        // Skipped and undecodable messages need no further handling; events
+        // and unsupported messages are processed once the caller says so
+        match result {
+            Ok(Some(_)) | Err(ConsumerError::UnsupportedVersion { .. }) => {
+                self.offsets
+                    .deliver(message.topic(), message.partition(), message.offset())
+            }
+            _ => self
+                .offsets
+                .process(message.topic(), message.partition(), message.offset()),
+        }
+
+        result
+    }
+
+    /// Mark the event most recently returned by `poll` as processed
+    ///
+    /// Its offset is included in the next `commit`.
+    pub fn mark_processed(&mut self) {
+        self.offsets.mark_processed();
+    }
+
+    /// Seek back so the message most recently returned by `poll` is read again
+    ///
+    /// Used when the caller cannot handle the message yet, e.g. because the
+    /// dead letter queue is unavailable.
+    pub fn redeliver(&mut self, timeout: Duration) -> Result<(), ConsumerError> {
+        if let Some((topic, partition, offset)) = self.offsets.delivered.take() {
+            self.consumer
+                .seek(&topic, partition, Offset::Offset(offset), timeout)
+                .map_err(ConsumerError::Seek)?;
+        }
+        Ok(())
+    }
+
+    /// Synchronously commit the offsets of all processed messages
+    ///
+    /// Does nothing if no message was processed since the last commit.
+    pub fn commit(&mut self) -> Result<(), ConsumerError> {
+        let offsets = self.offsets.take_processed();
+        if offsets.is_empty() {
+            return Ok(());
+        }
+
+        let mut partitions = TopicPartitionList::new();
+        for (topic, partition, offset) in &offsets {
+            partitions
+                .add_partition_offset(topic, *partition, Offset::Offset(*offset))
+                .map_err(ConsumerError::Commit)?;
+        }
+        self.consumer
+            .commit(&partitions, CommitMode::Sync)
+            .map_err(ConsumerError::Commit)
+    }
+
+    /// Seek newly assigned partitions to their explicit start offsets
+    ///
+    /// Partitions whose seek failed, and those not reached yet, are kept for
+    /// the next poll to retry.
+    fn seek_assigned(&mut self, timeout: Duration) -> Result<(), ConsumerError> {
+        let assigned = std::mem::take(
+            &mut *self
+                .consumer
+                .context()
+                .assigned
+                .lock()
+                .unwrap_or_else(|e| e.into_inner()),
+        );
+
+        let consumer = &self.consumer;
+        let result = apply_start_offsets(
+            assigned,
+            &mut self.pending_seeks,
+            |topic, partition, offset| {
+                consumer.seek(topic, partition, Offset::Offset(offset), timeout)
+            },
+        );
+
+        result.map_err(|(e, unapplied)| {
+            let mut assigned = consumer
+                .context()
+                .assigned
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            assigned.splice(0..0, unapplied);
+            ConsumerError::Seek(e)
+        })
+    }
+
+    /// Get the topics this consumer reads from
+    pub fn topics(&self) -> &ConsumerTopics {
+        &self.topics
+    }
+}
+
+/// Seek assigned partitions that have a pending explicit start offset
+///
+/// A start offset is removed from `pending` only once its seek succeeded. On
+/// failure the error is returned with the partitions still to handle, starting
+/// with the one that failed.
+fn apply_start_offsets<F>(
+    assigned: Vec<(String, i32)>,
+    pending: &mut HashMap<(String, i32), i64>,
+    mut seek: F,
+) -> Result<(), (KafkaError, Vec<(String, i32)>)>
+where
+    F: FnMut(&str, i32, i64) -> Result<(), KafkaError>,
+{
+    for (i, key) in assigned.iter().enumerate() {
+        let Some(&offset) = pending.get(key) else {
+            continue;
+        };
+        if let Err(e) = seek(&key.0, key.1, offset) {
+            return Err((e, assigned[i..].to_vec()));
+        }
+        pending.remove(key);
+    }
+    Ok(())
+}
+
+/// Decode a consumed message into a topology event
+///
+/// Returns `Ok(None)` for messages without a payload, for topics Atlas does
+/// not consume and for events before `start_block`.
+fn decode_message<M: Message>(
+    topics: &ConsumerTopics,
+    start_block: Option<u64>,
+    message: &M,
+) -> Result<Option<SpaceTopologyEvent>, ConsumerError> {
+    let Some(payload) = message.payload() else {
+        return Ok(None);
+    };
+
+    let version = parse_schema_version(schema_version_header(message));
+    let decoded =
+        version.and_then(|version| topics.decode_versioned(version, message.topic(), payload));
+
+    match decoded {
+        Some(result) => {
+            let event = result.map_err(|source| ConsumerError::Decode {
+                topic: message.topic().to_string(),
+                partition: message.partition(),
+                offset: message.offset(),
+                source,
+            })?;
+            Ok(event
+                .filter(|event| start_block.is_none_or(|block| event.meta.block_number >= block)))
+        }
+        None => Err(ConsumerError::UnsupportedVersion {
+            version,
+            message: Box::new(DeadLetter::from_message(message)),
+        }),
+    }
+}
+
+/// Get the raw `schema-version` header of a message, if present
+fn schema_version_header<M: Message>(message: &M) -> Option<&[u8]> {
+    message
+        .headers()?
+        .iter()
+        .find(|header| header.key == SCHEMA_VERSION_HEADER)
+        .and_then(|header| header.value)
+}
+
+impl std::fmt::Debug for AtlasConsumer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AtlasConsumer")
+            .field("topics", &self.topics)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::SpaceTopologyPayload;
+    use hermes_schema::pb::blockchain_metadata::BlockchainMetadata;
+    use hermes_schema::pb::space::{
+        hermes_create_space, hermes_space_trust_extension, HermesCreateSpace,
+        HermesSpaceTrustExtension, PersonalSpacePayload, RelatedExtension,
+    };
+    use prost::Message as _;
+    use rdkafka::message::{Header, OwnedHeaders, OwnedMessage};
+    use rdkafka::Timestamp;
+
+    fn make_meta() -> Option<BlockchainMetadata> {
+        Some(BlockchainMetadata {
+            created_at: 1_700_000_000,
+            created_by: vec![],
+            block_number: 7,
+            cursor: "cursor_7".to_string(),
+        })
+    }
+
+    #[test]
+    fn test_decode_by_topic() {
+        let topics = ConsumerTopics::default();
+
+        let created = HermesCreateSpace {
+            space_id: vec![1; 16],
+            topic_id: vec![2; 16],
+            payload: Some(hermes_create_space::Payload::PersonalSpace(
+                PersonalSpacePayload { owner: vec![3; 32] },
+            )),
+            meta: make_meta(),
+        };
+        let event = topics
+            .decode(SPACE_CREATIONS_TOPIC, &created.encode_to_vec())
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            event.payload,
+            SpaceTopologyPayload::SpaceCreated(_)
+        ));
+
+        let extended = HermesSpaceTrustExtension {
+            source_space_id: vec![1; 16],
+            extension: Some(hermes_space_trust_extension::Extension::Related(
+                RelatedExtension {
+                    target_space_id: vec![4; 16],
+                },
+            )),
+            meta: make_meta(),
+        };
+        let event = topics
+            .decode(TRUST_EXTENSIONS_TOPIC, &extended.encode_to_vec())
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            event.payload,
+            SpaceTopologyPayload::TrustExtended(_)
+        ));
+
+        // Messages from other topics are ignored
+        assert!(topics
+            .decode("knowledge.edits", &extended.encode_to_vec())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_decode_wrong_topic_fails() {
+        // A trust extension read from the creations topic is not a valid space
+        let extended = HermesSpaceTrustExtension {
+            source_space_id: vec![1; 16],
+            extension: None,
+            meta: make_meta(),
+        };
+        assert!(ConsumerTopics::default()
+            .decode(SPACE_CREATIONS_TOPIC, &extended.encode_to_vec())
+            .is_err());
+    }
+
+    #[test]
+    fn test_decode_versioned() {
+        let topics = ConsumerTopics::default();
+        let created = HermesCreateSpace {
+            space_id: vec![1; 16],
+            topic_id: vec![2; 16],
+            payload: Some(hermes_create_space::Payload::PersonalSpace(
+                PersonalSpacePayload { owner: vec![3; 32] },
+            )),
+            meta: make_meta(),
+        };
+        let payload = created.encode_to_vec();
+
+        assert!(matches!(
+            topics.decode_versioned(1, SPACE_CREATIONS_TOPIC, &payload),
+            Some(Ok(Some(_)))
+        ));
+
+        // Messages from a newer producer are not decoded with the old schema
+        assert!(topics
+            .decode_versioned(2, SPACE_CREATIONS_TOPIC, &payload)
+            .is_none());
+
+        // Through the consumer path they are rejected with the raw message
+        let message = |version: &str| {
+            OwnedMessage::new(
+                Some(payload.clone()),
+                None,
+                SPACE_CREATIONS_TOPIC.to_string(),
+                Timestamp::NotAvailable,
+                1,
+                3,
+                Some(OwnedHeaders::new().insert(Header {
+                    key: SCHEMA_VERSION_HEADER,
+                    value: Some(version),
+                })),
+            )
+        };
+        assert!(matches!(
+            decode_message(&topics, None, &message("1")),
+            Ok(Some(_))
+        ));
+
+        let err = decode_message(&topics, None, &message("2")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unsupported schema version 2 at space.creations[1]@3"
+        );
+        let ConsumerError::UnsupportedVersion { version, message } = err else {
+            panic!("expected an unsupported version error");
+        };
+        assert_eq!(version, Some(2));
+        assert_eq!(message.payload, Some(payload));
+        assert_eq!(
+            message.headers,
+            vec![(SCHEMA_VERSION_HEADER.to_string(), Some(b"2".to_vec()))]
+        );
+    }
+
+    #[test]
+    fn test_parse_start_config() {
+        assert_eq!("latest".parse(), Ok(OffsetReset::Latest));
+        assert!("newest".parse::<OffsetReset>().is_err());
+
+        assert_eq!(
+            "space.trust.extensions:2:1500".parse(),
+            Ok(PartitionOffset {
+                topic: TRUST_EXTENSIONS_TOPIC.to_string(),
+                partition: 2,
+                offset: 1500,
+            })
+        );
+        assert!("space.creations:1".parse::<PartitionOffset>().is_err());
+        assert!(":1:2".parse::<PartitionOffset>().is_err());
+        assert!("space.creations:x:2".parse::<PartitionOffset>().is_err());
+        assert_eq!(
+            "space.creations:0:-5".parse::<PartitionOffset>(),
+            Err("offset must not be negative, got space.creations:0:-5".to_string())
+        );
+        assert!("space.creations:0:0".parse::<PartitionOffset>().is_ok());
+    }
+
+    #[test]
+    fn test_offset_tracker_holds_back_delivered_event() {
+        let mut offsets = OffsetTracker::default();
+
+        offsets.process(SPACE_CREATIONS_TOPIC, 0, 4);
+        offsets.process(TRUST_EXTENSIONS_TOPIC, 1, 8);
+        offsets.deliver(SPACE_CREATIONS_TOPIC, 0, 5);
+
+        // The delivered event is not committed until it is processed
+        assert_eq!(
+            offsets.take_processed(),
+            vec![
+                (SPACE_CREATIONS_TOPIC.to_string(), 0, 5),
+                (TRUST_EXTENSIONS_TOPIC.to_string(), 1, 9),
+            ]
+        );
+        assert!(offsets.take_processed().is_empty());
+
+        offsets.mark_processed();
+        assert_eq!(
+            offsets.take_processed(),
+            vec![(SPACE_CREATIONS_TOPIC.to_string(), 0, 6)]
+        );
+
+        // Marking again without a new delivery is a no-op
+        offsets.mark_processed();
+        assert!(offsets.take_processed().is_empty());
+    }
+
+    #[test]
+    fn test_start_offsets_kept_until_seek_succeeds() {
+        let key = |topic: &str, partition| (topic.to_string(), partition);
+        let assigned = vec![
+            key(SPACE_CREATIONS_TOPIC, 0),
+            key(SPACE_CREATIONS_TOPIC, 1),
+            key(TRUST_EXTENSIONS_TOPIC, 0),
+        ];
+        let mut pending = HashMap::from([
+            (key(SPACE_CREATIONS_TOPIC, 0), 10),
+            (key(TRUST_EXTENSIONS_TOPIC, 0), 20),
+        ]);
+
+        // The first seek fails: nothing is applied and every partition is kept
+        let mut seeks = Vec::new();
+        let (_, unapplied) =
+            apply_start_offsets(assigned, &mut pending, |topic, partition, offset| {
+                seeks.push((topic.to_string(), partition, offset));
+                Err(KafkaError::Seek("Erroneous state".to_string()))
+            })
+            .unwrap_err();
+        assert_eq!(seeks, vec![(SPACE_CREATIONS_TOPIC.to_string(), 0, 10)]);
+        assert_eq!(
+            unapplied,
+            vec![
+                key(SPACE_CREATIONS_TOPIC, 0),
+                key(SPACE_CREATIONS_TOPIC, 1),
+                key(TRUST_EXTENSIONS_TOPIC, 0),
+            ]
+        );
+        assert_eq!(pending.len(), 2);
+
+        // A retry seeks both partitions with a start offset and drops them
+        let mut seeks = Vec::new();
+        apply_start_offsets(unapplied, &mut pending, |topic, partition, offset| {
+            seeks.push((topic.to_string(), partition, offset));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(
+            seeks,
+            vec![
+                (SPACE_CREATIONS_TOPIC.to_string(), 0, 10),
+                (TRUST_EXTENSIONS_TOPIC.to_string(), 0, 20),
+            ]
+        );
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn test_consumer_error_display() {
+        let err = ConsumerError::Decode {
+            topic: SPACE_CREATIONS_TOPIC.to_string(),
+            partition: 0,
+            offset: 12,
+            source: DecodeError::MissingField("meta"),
+        };
+        assert_eq!(
+            err.to_string(),
+            "failed to decode message at space.creations[0]@12: missing field: meta"
+        );
+    }
+}
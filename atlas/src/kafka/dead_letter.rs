@@ -0,0 +1,169 @@
+//! Dead letter queue for messages Atlas cannot process
+//!
+//! Messages written with a schema version Atlas does not know yet are
+//! republished verbatim (key, payload and headers) to a dead letter topic so
+//! they can be replayed once Atlas is upgraded. Headers recording where the
+//! message was consumed from and why it was rejected are appended.
+
+use crate::kafka::{AtlasProducer, ProducerError};
+use rdkafka::message::Headers;
+use rdkafka::Message;
+
+/// Header naming the topic a dead-lettered message was consumed from
+pub const SOURCE_TOPIC_HEADER: &str = "dlq-source-topic";
+
+/// Header with the partition a dead-lettered message was consumed from
+pub const SOURCE_PARTITION_HEADER: &str = "dlq-source-partition";
+
+/// Header with the offset a dead-lettered message was consumed from
+pub const SOURCE_OFFSET_HEADER: &str = "dlq-source-offset";
+
+/// Header describing why a message was dead-lettered
+pub const REASON_HEADER: &str = "dlq-reason";
+
+/// A consumed message kept verbatim for the dead letter queue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadLetter {
+    /// Topic the message was consumed from
+    pub topic: String,
+    /// Partition the message was consumed from
+    pub partition: i32,
+    /// Offset of the message in its partition
+    pub offset: i64,
+    /// Original message key
+    pub key: Option<Vec<u8>>,
+    /// Original message payload
+    pub payload: Option<Vec<u8>>,
+    /// Original message headers, in order
+    pub headers: Vec<(String, Option<Vec<u8>>)>,
+}
+
+impl DeadLetter {
+    /// Copy a consumed message
+    pub fn from_message<M: Message>(message: &M) -> Self {
+        let headers = message
+            .headers()
+            .map(|headers| {
+                headers
+                    .iter()
+                    .map(|header| (header.key.to_string(), header.value.map(<[u8]>::to_vec)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            topic: message.topic().to_string(),
+            partition: message.partition(),
+            offset: message.offset(),
+            key: message.key().map(<[u8]>::to_vec),
+            payload: message.payload().map(<[u8]>::to_vec),
+            headers,
+        }
+    }
+
+    /// Headers to publish: the original headers, then the source position and reason
+    pub fn dead_letter_headers(&self, reason: &str) -> Vec<(String, Option<Vec<u8>>)> {
+        let mut headers = self.headers.clone();
+        headers.extend(
+            [
+                (SOURCE_TOPIC_HEADER, self.topic.clone()),
+                (SOURCE_PARTITION_HEADER, self.partition.to_string()),
+                (SOURCE_OFFSET_HEADER, self.offset.to_string()),
+                (REASON_HEADER, reason.to_string()),
+            ]
+            .map(|(key, value)| (key.to_string(), Some(value.into_bytes()))),
+        );
+        headers
+    }
+}
+
+/// Publishes rejected messages to the dead letter topic
+pub struct DeadLetterEmitter {
+    producer: AtlasProducer,
+}
+
+impl DeadLetterEmitter {
+    /// Create a new emitter with the given producer
+    pub fn new(producer: AtlasProducer) -> Self {
+        Self { producer }
+    }
+
+    /// Publish a message to the dead letter topic and wait for delivery
+    pub fn emit(&self, letter: &DeadLetter, reason: &str) -> Result<(), ProducerError> {
+        self.producer.send_with_headers(
+            letter.key.as_deref(),
+            letter.payload.as_deref(),
+            &letter.dead_letter_headers(reason),
+        )?;
+        self.producer.flush()
+    }
+}
+
+impl std::fmt::Debug for DeadLetterEmitter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeadLetterEmitter")
+            .field("topic", &self.producer.topic())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rdkafka::message::{Header, OwnedHeaders, OwnedMessage};
+    use rdkafka::Timestamp;
+
+    #[test]
+    fn test_dead_letter_keeps_message_verbatim() {
+        let headers = OwnedHeaders::new()
+            .insert(Header {
+                key: "schema-version",
+                value: Some("2"),
+            })
+            .insert(Header {
+                key: "empty",
+                value: None::<&[u8]>,
+            });
+        let message = OwnedMessage::new(
+            Some(vec![1, 2, 3]),
+            Some(b"space".to_vec()),
+            "space.creations".to_string(),
+            Timestamp::NotAvailable,
+            4,
+            17,
+            Some(headers),
+        );
+
+        let letter = DeadLetter::from_message(&message);
+        assert_eq!(letter.topic, "space.creations");
+        assert_eq!(letter.partition, 4);
+        assert_eq!(letter.offset, 17);
+        assert_eq!(letter.key.as_deref(), Some(&b"space"[..]));
+        assert_eq!(letter.payload.as_deref(), Some(&[1, 2, 3][..]));
+
+        let headers = letter.dead_letter_headers("unsupported schema version 2");
+        let as_str = |value: &Option<Vec<u8>>| {
+            value
+                .as_deref()
+                .map(|v| String::from_utf8(v.to_vec()).unwrap())
+        };
+        let headers: Vec<(&str, Option<String>)> = headers
+            .iter()
+            .map(|(key, value)| (key.as_str(), as_str(value)))
+            .collect();
+        assert_eq!(
+            headers,
+            vec![
+                ("schema-version", Some("2".to_string())),
+                ("empty", None),
+                (SOURCE_TOPIC_HEADER, Some("space.creations".to_string())),
+                (SOURCE_PARTITION_HEADER, Some("4".to_string())),
+                (SOURCE_OFFSET_HEADER, Some("17".to_string())),
+                (
+                    REASON_HEADER,
+                    Some("unsupported schema version 2".to_string())
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,58 @@
+//! Canonical diff emitter
+//!
+//! Emits `CanonicalGraphDiff` messages to Kafka when running in incremental
+//! mode. Diffs only describe canonical set membership changes; full graphs are
+//! still emitted periodically by `CanonicalGraphEmitter` for resync.
+
+use crate::events::{BlockMetadata, SpaceId};
+use crate::graph::CanonicalDelta;
+use crate::kafka::{AtlasProducer, ProducerError};
+use hermes_schema::pb::blockchain_metadata::BlockchainMetadata as ProtoBlockchainMetadata;
+use hermes_schema::pb::topology::CanonicalGraphDiff;
+use prost::Message;
+
+/// Emits canonical set diffs to Kafka
+pub struct CanonicalDiffEmitter {
+    producer: AtlasProducer,
+}
+
+impl CanonicalDiffEmitter {
+    /// Create a new emitter with the given producer
+    pub fn new(producer: AtlasProducer) -> Self {
+        Self { producer }
+    }
+
+    /// Emit a canonical set diff to Kafka, keyed by root
+    pub fn emit(
+        &self,
+        root: &SpaceId,
+        delta: &CanonicalDelta,
+        meta: &BlockMetadata,
+    ) -> Result<(), ProducerError> {
+        let diff = CanonicalGraphDiff {
+            root_id: root.to_vec(),
+            added_space_ids: delta.added.iter().map(|id| id.to_vec()).collect(),
+            removed_space_ids: delta.removed.iter().map(|id| id.to_vec()).collect(),
+            meta: Some(ProtoBlockchainMetadata {
+                created_at: meta.block_timestamp,
+                created_by: Vec::new(),
+                block_number: meta.block_number,
+                cursor: meta.cursor.clone(),
+            }),
+        };
+
+        let mut payload = Vec::with_capacity(diff.encoded_len());
+        diff.encode(&mut payload)
+            .expect("Vec<u8> provides sufficient buffer capacity");
+
+        self.producer.send_and_flush(root, &payload)
+    }
+}
+
+impl std::fmt::Debug for CanonicalDiffEmitter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CanonicalDiffEmitter")
+            .field("topic", &self.producer.topic())
+            .finish_non_exhaustive()
+    }
+}
@@ -24,12 +24,12 @@
 //! ```
 
 use crate::events::BlockMetadata;
-use crate::graph::{CanonicalGraph, EdgeType, TreeNode};
+use crate::graph::{CanonicalGraph, EdgeType, TreeNode, TrustPath};
 use crate::kafka::{AtlasProducer, ProducerError};
 use hermes_schema::pb::blockchain_metadata::BlockchainMetadata as ProtoBlockchainMetadata;
 use hermes_schema::pb::topology::{
     canonical_tree_node::Edge, CanonicalGraphUpdated, CanonicalTreeNode, RelatedEdge, RootEdge,
-    TopicEdge, VerifiedEdge,
+    SpaceTrust, TopicEdge, TrustPathType, VerifiedEdge,
 };
 use prost::Message;
 
@@ -58,6 +58,11 @@ impl CanonicalGraphEmitter {
                 block_number: meta.block_number,
                 cursor: meta.cursor.clone(),
             }),
+            space_trust: graph
+                .trust_paths()
+                .iter()
+                .map(trust_path_to_proto)
+                .collect(),
         };
 
         let mut payload = Vec::with_capacity(update.encoded_len());
@@ -89,6 +94,22 @@ fn tree_node_to_proto(node: &TreeNode) -> CanonicalTreeNode {
     }
 }
 
+fn trust_path_to_proto(path: &TrustPath) -> SpaceTrust {
+    let path_type = match path.path_type {
+        EdgeType::Root => TrustPathType::Root,
+        EdgeType::Verified => TrustPathType::Verified,
+        EdgeType::Related => TrustPathType::Related,
+        EdgeType::Topic => TrustPathType::Topic,
+    };
+
+    SpaceTrust {
+        space_id: path.space_id.to_vec(),
+        depth: path.depth,
+        parent_space_id: path.parent.map(|id| id.to_vec()).unwrap_or_default(),
+        path_type: path_type as i32,
+    }
+}
+
 impl std::fmt::Debug for CanonicalGraphEmitter {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("CanonicalGraphEmitter")
@@ -1,10 +1,26 @@
 //! Kafka integration for Atlas
 //!
-//! This module provides Kafka producer functionality for emitting
-//! canonical graph updates to downstream consumers.
+//! This module provides a Kafka consumer for the space topology topics
+//! published by hermes-processor, and producer functionality for emitting
+//! canonical graph updates, incremental canonical diffs, trust anomaly
+//! alerts and dead-lettered messages to downstream consumers.
 
+mod anomaly_emitter;
+mod consumer;
+mod dead_letter;
+mod diff_emitter;
 mod emitter;
 mod producer;
 
+pub use anomaly_emitter::TrustAnomalyEmitter;
+pub use consumer::{
+    AtlasConsumer, ConsumerError, ConsumerStart, ConsumerTopics, OffsetReset, PartitionOffset,
+    SPACE_CREATIONS_TOPIC, TRUST_EXTENSIONS_TOPIC,
+};
+pub use dead_letter::{
+    DeadLetter, DeadLetterEmitter, REASON_HEADER, SOURCE_OFFSET_HEADER, SOURCE_PARTITION_HEADER,
+    SOURCE_TOPIC_HEADER,
+};
+pub use diff_emitter::CanonicalDiffEmitter;
 pub use emitter::CanonicalGraphEmitter;
 pub use producer::{AtlasProducer, ProducerError};
@@ -5,6 +5,7 @@
 
 use rdkafka::config::ClientConfig;
 use rdkafka::error::KafkaError;
+use rdkafka::message::{Header, OwnedHeaders};
 use rdkafka::producer::{BaseProducer, BaseRecord, Producer};
 use std::time::Duration;
 
@@ -73,23 +74,7 @@ impl AtlasProducer {
             .set("queue.buffering.max.kbytes", "1048576") // 1GB buffer
             .set("batch.num.messages", "10000");
 
-        // If SASL credentials are provided, enable SASL/SSL (for managed Kafka)
-        // Otherwise, use plaintext (for local development)
-        if let (Ok(username), Ok(password)) = (
-            std::env::var("KAFKA_USERNAME"),
-            std::env::var("KAFKA_PASSWORD"),
-        ) {
-            config
-                .set("security.protocol", "SASL_SSL")
-                .set("sasl.mechanisms", "PLAIN")
-                .set("sasl.username", &username)
-                .set("sasl.password", &password);
-
-            // Use custom CA certificate if provided (PEM format string)
-            if let Ok(ca_pem) = std::env::var("KAFKA_SSL_CA_PEM") {
-                config.set("ssl.ca.pem", &ca_pem);
-            }
-        }
+        apply_security_config(&mut config);
 
         let producer = config.create().map_err(ProducerError::Creation)?;
 
@@ -118,6 +103,40 @@ impl AtlasProducer {
         Ok(())
     }
 
+    /// Send a message with headers to Kafka
+    ///
+    /// The key and payload are optional so messages can be republished
+    /// exactly as they were consumed. Like `send()`, this does not flush.
+    pub fn send_with_headers(
+        &self,
+        key: Option<&[u8]>,
+        payload: Option<&[u8]>,
+        headers: &[(String, Option<Vec<u8>>)],
+    ) -> Result<(), ProducerError> {
+        let headers = headers
+            .iter()
+            .fold(OwnedHeaders::new(), |headers, (key, value)| {
+                headers.insert(Header {
+                    key,
+                    value: value.as_deref(),
+                })
+            });
+
+        let mut record = BaseRecord::<[u8], [u8]>::to(&self.topic).headers(headers);
+        if let Some(key) = key {
+            record = record.key(key);
+        }
+        if let Some(payload) = payload {
+            record = record.payload(payload);
+        }
+
+        self.producer
+            .send(record)
+            .map_err(|(e, _)| ProducerError::Send(e))?;
+
+        Ok(())
+    }
+
     /// Flush all buffered messages to Kafka
     ///
     /// Blocks until all messages are delivered or the timeout is reached.
@@ -142,6 +161,28 @@ impl AtlasProducer {
     }
 }
 
+/// Apply SASL/SSL settings from the environment to a client config
+///
+/// If SASL credentials are provided, enable SASL/SSL (for managed Kafka).
+/// Otherwise, use plaintext (for local development).
+pub(super) fn apply_security_config(config: &mut ClientConfig) {
+    if let (Ok(username), Ok(password)) = (
+        std::env::var("KAFKA_USERNAME"),
+        std::env::var("KAFKA_PASSWORD"),
+    ) {
+        config
+            .set("security.protocol", "SASL_SSL")
+            .set("sasl.mechanisms", "PLAIN")
+            .set("sasl.username", &username)
+            .set("sasl.password", &password);
+
+        // Use custom CA certificate if provided (PEM format string)
+        if let Ok(ca_pem) = std::env::var("KAFKA_SSL_CA_PEM") {
+            config.set("ssl.ca.pem", &ca_pem);
+        }
+    }
+}
+
 impl std::fmt::Debug for AtlasProducer {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("AtlasProducer")
@@ -5,7 +5,10 @@
 //! - Group abstractions with dynamic resolution at query time
 //! - Trust model based on reachability from root
 
+pub mod anomaly;
 pub mod convert;
+pub mod decode;
 pub mod events;
 pub mod graph;
 pub mod kafka;
+pub mod snapshot;
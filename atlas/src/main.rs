@@ -5,48 +5,203 @@
 //! and publishes updates to Kafka.
 
 use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
+use atlas::anomaly::{AnomalyConfig, TrustRateDetector};
 use atlas::convert::convert_mock_blocks;
-use atlas::events::{SpaceId, SpaceTopologyEvent, SpaceTopologyPayload};
-use atlas::graph::{CanonicalProcessor, GraphState, TransitiveProcessor};
-use atlas::kafka::{AtlasProducer, CanonicalGraphEmitter};
+use atlas::events::{BlockMetadata, SpaceId, SpaceTopologyEvent, SpaceTopologyPayload};
+use atlas::graph::{
+    CanonicalProcessor, DiskColdStore, GraphState, IslandPruner, PruneConfig, TransitiveProcessor,
+};
+use atlas::kafka::{
+    AtlasConsumer, AtlasProducer, CanonicalDiffEmitter, CanonicalGraphEmitter, ConsumerError,
+    ConsumerStart, ConsumerTopics, DeadLetter, DeadLetterEmitter, TrustAnomalyEmitter,
+};
+use atlas::snapshot::{
+    ArchiveRecord, FileSnapshotStore, GraphSnapshot, SnapshotError, SnapshotStore,
+};
 
 // Use the shared mock_substream crate
 use mock_substream::test_topology;
 
+/// How long a single Kafka poll waits before checking for shutdown
+const POLL_TIMEOUT: Duration = Duration::from_millis(500);
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let broker = env::var("KAFKA_BROKER").unwrap_or_else(|_| "localhost:9092".to_string());
+    let kafka_source = kafka_source_from_env()?;
+    let root_space = root_space_from_env()?;
     let topic = env::var("KAFKA_TOPIC").unwrap_or_else(|_| "topology.canonical".to_string());
+    let alert_topic =
+        env::var("KAFKA_ALERT_TOPIC").unwrap_or_else(|_| "topology.alerts".to_string());
+    let anomaly_config = anomaly_config_from_env()?;
+    let incremental = incremental_mode_from_env()?;
+    let diff_topic =
+        env::var("KAFKA_DIFF_TOPIC").unwrap_or_else(|_| "topology.canonical.diffs".to_string());
+    let snapshot_interval: usize = match env::var("SNAPSHOT_INTERVAL") {
+        Ok(interval) => interval.parse()?,
+        Err(_) => 100,
+    };
+    let prune_config = prune_config_from_env()?;
+    let prune_interval: usize = match env::var("PRUNE_INTERVAL") {
+        Ok(interval) => interval.parse()?,
+        Err(_) => 1000,
+    };
+    let cold_store_dir =
+        env::var("PRUNE_COLD_STORE_DIR").unwrap_or_else(|_| "atlas-islands".to_string());
+    let mut state_store = env::var("STATE_SNAPSHOT_PATH")
+        .ok()
+        .map(FileSnapshotStore::new);
+    let state_snapshot_interval: u64 = match env::var("STATE_SNAPSHOT_INTERVAL") {
+        Ok(interval) => interval.parse()?,
+        Err(_) => 1000,
+    };
 
     println!("╔══════════════════════════════════════════════════════════════════════════════╗");
     println!("║                     Atlas Topology Processor                                 ║");
     println!("╚══════════════════════════════════════════════════════════════════════════════╝");
     println!();
     println!("Kafka broker: {}", broker);
+    if kafka_source {
+        let topics = consumer_topics_from_env();
+        println!(
+            "Input topics: {}, {}",
+            topics.creations, topics.trust_extensions
+        );
+    }
     println!("Output topic: {}", topic);
+    println!("Alert topic:  {}", alert_topic);
+    if incremental {
+        println!("Diff topic:   {}", diff_topic);
+        println!(
+            "Mode: incremental (full snapshot every {} diffs)",
+            snapshot_interval
+        );
+    } else {
+        println!("Mode: full recomputation");
+    }
+    println!(
+        "Anomaly threshold: {} edges per {}s",
+        anomaly_config.max_edges, anomaly_config.window_seconds
+    );
+    if let Some(config) = &prune_config {
+        println!(
+            "Pruning: islands idle for {} blocks, checked every {} events",
+            config.stale_blocks, prune_interval
+        );
+        println!("Cold store: {}", cold_store_dir);
+    }
+    if let Some(store) = &state_store {
+        println!(
+            "State snapshots: {} every {} blocks",
+            store.path().display(),
+            state_snapshot_interval
+        );
+    }
     println!();
 
-    // Set up Kafka producer
+    // Set up Kafka producers
     let producer = AtlasProducer::new(&broker, &topic)?;
     let emitter = CanonicalGraphEmitter::new(producer);
+    let alert_producer = AtlasProducer::new(&broker, &alert_topic)?;
+    let anomaly_emitter = TrustAnomalyEmitter::new(alert_producer);
+    let diff_emitter = if incremental {
+        Some(CanonicalDiffEmitter::new(AtlasProducer::new(
+            &broker,
+            &diff_topic,
+        )?))
+    } else {
+        None
+    };
 
-    // Generate deterministic topology from shared mock_substream crate
-    let blocks = test_topology::generate();
-    let events = convert_mock_blocks(&blocks);
+    // Read events from Kafka, or generate the deterministic mock topology
+    let mut source = if kafka_source {
+        let group_id = env::var("KAFKA_GROUP_ID").unwrap_or_else(|_| "atlas".to_string());
+        let start = consumer_start_from_env()?;
+        if let Some(block) = start.start_block {
+            println!("Skipping events before block {}", block);
+        }
+        let consumer = AtlasConsumer::new(&broker, &group_id, consumer_topics_from_env(), start)?;
+        let dlq_topic = env::var("KAFKA_DLQ_TOPIC").unwrap_or_else(|_| "topology.dlq".to_string());
+        let dead_letters = DeadLetterEmitter::new(AtlasProducer::new(&broker, &dlq_topic)?);
+        println!("Dead letter topic: {}", dlq_topic);
 
-    let root_space = test_topology::ROOT_SPACE_ID;
+        // Stop consuming on SIGINT/SIGTERM, finishing the current event first
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&shutdown);
+        ctrlc::set_handler(move || flag.store(true, Ordering::SeqCst))?;
 
-    println!(
-        "Generated {} topology events from mock substream",
-        events.len()
-    );
+        println!("Consuming topology events as group {}", group_id);
+        EventSource::Kafka {
+            consumer: Box::new(consumer),
+            dead_letters,
+            shutdown,
+        }
+    } else {
+        let blocks = test_topology::generate();
+        let events = convert_mock_blocks(&blocks);
+        println!(
+            "Generated {} topology events from mock substream",
+            events.len()
+        );
+        EventSource::Mock(events.into_iter())
+    };
     println!("Root space: {}", format_space_id(root_space));
     println!();
 
-    // Create graph state and processors
-    let mut state = GraphState::new();
+    // Restore graph state from the last snapshot, if any
+    //
+    // Snapshots always cover whole blocks. The Kafka consumer resumes from
+    // the offsets committed with the snapshot; the mock topology is replayed
+    // from the start, so skip the blocks the snapshot already contains.
+    let mut resume_after: Option<u64> = None;
+    let mut last_snapshot_block = 0;
+    let mut restored_meta: Option<BlockMetadata> = None;
+    let mut restored_archive: Option<ArchiveRecord> = None;
+    let mut state = match state_store.as_ref().map(|store| store.load()).transpose()? {
+        Some(Some(snapshot)) => {
+            println!(
+                "Restored state at block {} ({}): {} spaces",
+                snapshot.block_number,
+                snapshot.cursor,
+                snapshot.spaces.len()
+            );
+            println!();
+            if !kafka_source {
+                resume_after = Some(snapshot.block_number);
+            }
+            last_snapshot_block = snapshot.block_number;
+            restored_meta = Some(BlockMetadata {
+                block_number: snapshot.block_number,
+                block_timestamp: 0,
+                tx_hash: String::new(),
+                cursor: snapshot.cursor.clone(),
+            });
+            restored_archive = snapshot.archive.clone();
+            GraphState::restore(&snapshot)
+        }
+        _ => GraphState::new(),
+    };
+
+    // Create processors
     let mut transitive = TransitiveProcessor::new();
     let mut canonical_processor = CanonicalProcessor::new(root_space);
+    let mut detector = TrustRateDetector::new(anomaly_config);
+    let mut pruner = match prune_config {
+        Some(config) => {
+            let store = DiskColdStore::open(&cold_store_dir)?;
+            let mut pruner = IslandPruner::with_store(root_space, config, store);
+            // Also drops islands left in the store by a run without snapshots
+            pruner.restore_archive(&restored_archive.take().unwrap_or_default())?;
+            Some(pruner)
+        }
+        None => None,
+    };
+    if restored_archive.is_some_and(|archive| !archive.spaces.is_empty()) {
+        return Err("snapshot has archived islands; set PRUNE_STALE_BLOCKS to restore them".into());
+    }
 
     // Process each event
     println!("┌──────────────────────────────────────────────────────────────────────────────┐");
@@ -54,9 +209,74 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("├──────────────────────────────────────────────────────────────────────────────┤");
 
     let mut emit_count = 0;
+    let mut diffs_since_snapshot = 0;
+    let mut last_meta: Option<BlockMetadata> = None;
+
+    // Seed the incremental canonical set from the restored state, otherwise
+    // it only holds the root until the next full computation. In incremental
+    // mode, also publish the full graph so consumers have a base for the diffs.
+    if restored_meta.is_some() || diff_emitter.is_some() {
+        let initial = canonical_processor.compute(&state, &mut transitive);
+        if let (Some(graph), Some(_)) = (initial, &diff_emitter) {
+            let meta = restored_meta.clone().unwrap_or(BlockMetadata {
+                block_number: 0,
+                block_timestamp: 0,
+                tx_hash: String::new(),
+                cursor: String::new(),
+            });
+            emitter.emit(&graph, &meta)?;
+            emit_count += 1;
+            println!("│ Emitted initial canonical graph ({} nodes)", graph.len());
+        }
+    }
+
+    for i in 0.. {
+        let Some(event) = source.next_event() else {
+            break;
+        };
+        let event = &event;
+        // Skip events already reflected in the restored snapshot
+        if resume_after.is_some_and(|block| event.meta.block_number <= block) {
+            continue;
+        }
+
+        // Periodically persist the graph state once a block is complete, then
+        // commit the consumed offsets it covers
+        if let (Some(store), Some(meta)) = (state_store.as_mut(), &last_meta) {
+            if event.meta.block_number != meta.block_number
+                && meta.block_number >= last_snapshot_block + state_snapshot_interval
+            {
+                save_state_snapshot(store, &state, pruner.as_mut(), meta)?;
+                source.commit()?;
+                last_snapshot_block = meta.block_number;
+                println!(
+                    "│      └─▶ Saved state snapshot at block {}",
+                    meta.block_number
+                );
+            }
+        }
 
-    for (i, event) in events.iter().enumerate() {
         print_event(i, event);
+        last_meta = Some(event.meta.clone());
+
+        // Restore archived islands this event touches before applying it
+        if let Some(pruner) = pruner.as_mut() {
+            if pruner.rehydrate(event, &mut state)? > 0 {
+                println!("│      └─▶ Rehydrated archived island");
+            }
+            pruner.observe(event);
+        }
+
+        // Flag spaces extending trust at an abnormal rate
+        if let Some(anomaly) = detector.observe(event) {
+            anomaly_emitter.emit(&anomaly)?;
+            println!(
+                "│      └─▶ Trust rate anomaly: {} created {} edges in {}s",
+                format_space_id(anomaly.space_id),
+                anomaly.edge_count,
+                anomaly.window_seconds
+            );
+        }
 
         // Update transitive cache based on event
         transitive.handle_event(event, &state);
@@ -64,6 +284,43 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         // Apply event to graph state
         state.apply_event(event);
 
+        // Periodically archive stale non-canonical islands
+        if let Some(pruner) = pruner.as_mut() {
+            if prune_interval > 0 && (i + 1) % prune_interval == 0 {
+                let report = pruner.prune(&mut state, &mut transitive)?;
+                if report.islands > 0 {
+                    println!(
+                        "│      └─▶ Archived {} islands ({} spaces)",
+                        report.islands, report.spaces
+                    );
+                }
+                // Without snapshots no saved state refers to taken islands
+                if state_store.is_none() {
+                    pruner.retain_archived()?;
+                }
+            }
+        }
+
+        // In incremental mode, emit set diffs and only periodically snapshot
+        if let Some(diff_emitter) = &diff_emitter {
+            let delta = canonical_processor.apply_incremental(event, &state);
+            if !delta.is_empty() {
+                diff_emitter.emit(&root_space, &delta, &event.meta)?;
+                emit_count += 1;
+                diffs_since_snapshot += 1;
+                println!(
+                    "│      └─▶ Emitted canonical diff (+{} -{})",
+                    delta.added.len(),
+                    delta.removed.len()
+                );
+            }
+
+            if diffs_since_snapshot < snapshot_interval {
+                continue;
+            }
+            diffs_since_snapshot = 0;
+        }
+
         // Compute canonical graph and emit if changed
         if let Some(graph) = canonical_processor.compute(&state, &mut transitive) {
             emitter.emit(&graph, &event.meta)?;
@@ -76,6 +333,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
     println!("└──────────────────────────────────────────────────────────────────────────────┘");
 
+    // Persist the final state so a restart resumes from the last event
+    if let (Some(store), Some(meta)) = (state_store.as_mut(), &last_meta) {
+        save_state_snapshot(store, &state, pruner.as_mut(), meta)?;
+        source.commit()?;
+        println!("Saved state snapshot at block {}", meta.block_number);
+    }
+
     println!();
     println!("┌──────────────────────────────────────────────────────────────────────────────┐");
     println!("│ Summary                                                                      │");
@@ -96,6 +360,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         "│ Kafka messages sent: {:>4}                                                    │",
         emit_count
     );
+    println!(
+        "│ Trust anomalies:     {:>4}                                                    │",
+        detector.stats().anomalies_raised
+    );
     println!("└──────────────────────────────────────────────────────────────────────────────┘");
 
     println!();
@@ -104,6 +372,208 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Source of topology events
+enum EventSource {
+    /// Deterministic mock topology, processed once
+    Mock(std::vec::IntoIter<SpaceTopologyEvent>),
+    /// Live Kafka topics, consumed until shutdown is requested
+    Kafka {
+        consumer: Box<AtlasConsumer>,
+        dead_letters: DeadLetterEmitter,
+        shutdown: Arc<AtomicBool>,
+    },
+}
+
+impl EventSource {
+    /// Return the next event, or `None` once the source is exhausted or shut down
+    ///
+    /// Asking for the next event marks the previous one as processed.
+    /// Messages with an unknown schema version are published to the dead
+    /// letter queue; undecodable messages and receive errors are logged and
+    /// skipped so a single bad message does not stop the consumer.
+    fn next_event(&mut self) -> Option<SpaceTopologyEvent> {
+        match self {
+            EventSource::Mock(events) => events.next(),
+            EventSource::Kafka {
+                consumer,
+                dead_letters,
+                shutdown,
+            } => {
+                consumer.mark_processed();
+                while !shutdown.load(Ordering::SeqCst) {
+                    match consumer.poll(POLL_TIMEOUT) {
+                        Ok(Some(event)) => return Some(event),
+                        Ok(None) => {}
+                        Err(e) => match e.dead_letter() {
+                            Some(letter) => {
+                                if !dead_letter(consumer, dead_letters, letter, &e.to_string()) {
+                                    return None;
+                                }
+                            }
+                            None => match e {
+                                ConsumerError::Seek(_) => {
+                                    println!("│ Failed to seek consumer, stopping: {}", e);
+                                    return None;
+                                }
+                                _ => eprintln!("Skipping message: {}", e),
+                            },
+                        },
+                    }
+                }
+                println!("│ Shutdown requested, stopping consumer");
+                None
+            }
+        }
+    }
+
+    /// Commit the position of every processed event, if the source has one
+    fn commit(&mut self) -> Result<(), ConsumerError> {
+        match self {
+            EventSource::Mock(_) => Ok(()),
+            EventSource::Kafka { consumer, .. } => consumer.commit(),
+        }
+    }
+}
+
+/// Publish a message Atlas cannot decode to the dead letter queue
+///
+/// If publishing fails the consumer is rewound so the message is read again
+/// rather than lost. Returns `false` if the consumer could not be rewound and
+/// must stop.
+fn dead_letter(
+    consumer: &mut AtlasConsumer,
+    dead_letters: &DeadLetterEmitter,
+    letter: &DeadLetter,
+    reason: &str,
+) -> bool {
+    match dead_letters.emit(letter, reason) {
+        Ok(()) => {
+            eprintln!("Dead-lettered message: {}", reason);
+            consumer.mark_processed();
+            true
+        }
+        Err(e) => {
+            eprintln!("Failed to dead-letter message, retrying: {}", e);
+            if let Err(e) = consumer.redeliver(POLL_TIMEOUT) {
+                println!("│ Failed to rewind consumer, stopping: {}", e);
+                return false;
+            }
+            std::thread::sleep(POLL_TIMEOUT);
+            true
+        }
+    }
+}
+
+/// Save the graph state as of the end of a block
+///
+/// Archived islands stay in the cold store; the snapshot records the
+/// pruner's index of them. Once the snapshot is saved, islands rehydrated
+/// since the previous one are dropped from the store.
+fn save_state_snapshot(
+    store: &mut FileSnapshotStore,
+    state: &GraphState,
+    pruner: Option<&mut IslandPruner<DiskColdStore>>,
+    meta: &BlockMetadata,
+) -> Result<(), SnapshotError> {
+    let mut snapshot = GraphSnapshot::capture(state, &meta.cursor, meta.block_number);
+    if let Some(pruner) = &pruner {
+        snapshot = snapshot.with_archive(pruner.archive());
+    }
+    store.save(&snapshot)?;
+
+    match pruner {
+        Some(pruner) => pruner.retain_archived(),
+        None => Ok(()),
+    }
+}
+
+/// Read the event source from the environment
+fn kafka_source_from_env() -> Result<bool, Box<dyn std::error::Error>> {
+    match env::var("ATLAS_SOURCE").as_deref() {
+        Err(_) | Ok("mock") => Ok(false),
+        Ok("kafka") => Ok(true),
+        Ok(other) => Err(format!("unknown ATLAS_SOURCE: {}", other).into()),
+    }
+}
+
+/// Read the input topic names from the environment
+fn consumer_topics_from_env() -> ConsumerTopics {
+    let mut topics = ConsumerTopics::default();
+    if let Ok(creations) = env::var("KAFKA_CREATIONS_TOPIC") {
+        topics.creations = creations;
+    }
+    if let Ok(trust_extensions) = env::var("KAFKA_TRUST_TOPIC") {
+        topics.trust_extensions = trust_extensions;
+    }
+    topics
+}
+
+/// Read where the consumer starts reading from the environment
+fn consumer_start_from_env() -> Result<ConsumerStart, Box<dyn std::error::Error>> {
+    let mut start = ConsumerStart::default();
+
+    if let Ok(offset_reset) = env::var("KAFKA_OFFSET_RESET") {
+        start = start.with_offset_reset(offset_reset.parse()?);
+    }
+    if let Ok(offsets) = env::var("KAFKA_START_OFFSETS") {
+        for offset in offsets.split(',').filter(|offset| !offset.is_empty()) {
+            start = start.with_partition_offset(offset.trim().parse()?);
+        }
+    }
+    if let Ok(block) = env::var("KAFKA_START_BLOCK") {
+        start = start.with_start_block(block.parse()?);
+    }
+
+    Ok(start)
+}
+
+/// Read the root space ID from the environment, defaulting to the mock root
+fn root_space_from_env() -> Result<SpaceId, Box<dyn std::error::Error>> {
+    match env::var("ROOT_SPACE_ID") {
+        Ok(hex_id) => {
+            let mut id = [0u8; 16];
+            hex::decode_to_slice(hex_id.trim_start_matches("0x"), &mut id)?;
+            Ok(id)
+        }
+        Err(_) => Ok(test_topology::ROOT_SPACE_ID),
+    }
+}
+
+/// Read anomaly detection thresholds from the environment
+fn anomaly_config_from_env() -> Result<AnomalyConfig, Box<dyn std::error::Error>> {
+    let mut config = AnomalyConfig::default();
+
+    if let Ok(window) = env::var("ANOMALY_WINDOW_SECONDS") {
+        config = config.with_window_seconds(window.parse()?);
+    }
+    if let Ok(max_edges) = env::var("ANOMALY_MAX_EDGES") {
+        config = config.with_max_edges(max_edges.parse()?);
+    }
+
+    Ok(config)
+}
+
+/// Read the island pruning configuration from the environment
+///
+/// Pruning is disabled unless `PRUNE_STALE_BLOCKS` is set.
+fn prune_config_from_env() -> Result<Option<PruneConfig>, Box<dyn std::error::Error>> {
+    match env::var("PRUNE_STALE_BLOCKS") {
+        Ok(stale_blocks) => Ok(Some(
+            PruneConfig::default().with_stale_blocks(stale_blocks.parse()?),
+        )),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Read the canonical computation mode from the environment
+fn incremental_mode_from_env() -> Result<bool, Box<dyn std::error::Error>> {
+    match env::var("CANONICAL_MODE").as_deref() {
+        Err(_) | Ok("full") => Ok(false),
+        Ok("incremental") => Ok(true),
+        Ok(other) => Err(format!("unknown CANONICAL_MODE: {}", other).into()),
+    }
+}
+
 /// Format a space ID with a friendly name if known
 fn format_space_id(id: SpaceId) -> String {
     let last_byte = id[15];
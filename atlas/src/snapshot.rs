@@ -0,0 +1,701 @@
+//! Persistent graph state snapshots
+//!
+//! Lets Atlas restart without replaying the whole event stream. A
+//! `GraphSnapshot` captures every space, edge and edge provenance in a
+//! `GraphState` together with the cursor and block of the last processed
+//! event, and `GraphState::restore` rebuilds the state from it.
+//!
+//! Snapshots are stored as JSON with hex-encoded IDs. Records are sorted, so
+//! equal states produce identical files. `FileSnapshotStore` writes to a
+//! temporary file, syncs it and renames it into place, so a crash or power
+//! loss mid-write leaves the previous snapshot intact.
+//!
+//! Islands archived by an `IslandPruner` are not copied into the snapshot. It
+//! records the pruner's `ArchiveRecord` instead, and the islands stay in the
+//! pruner's cold store.
+//!
+//! `GraphState::to_snapshot` and `GraphState::from_snapshot` export and import
+//! a bare state without a stream position, for booting tests or tools from a
+//! fixture instead of replaying events.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use atlas::graph::GraphState;
+//! use atlas::snapshot::{FileSnapshotStore, GraphSnapshot, SnapshotStore};
+//!
+//! let mut store = FileSnapshotStore::new("/var/lib/atlas/state.json");
+//!
+//! let state = match store.load()? {
+//!     Some(snapshot) => GraphState::restore(&snapshot),
+//!     None => GraphState::new(),
+//! };
+//!
+//! store.save(&GraphSnapshot::capture(&state, "cursor_42", 42))?;
+//! # Ok::<(), atlas::snapshot::SnapshotError>(())
+//! ```
+
+use crate::events::{SpaceId, TopicId};
+use crate::graph::{EdgeMetadata, EdgeType, GraphState, IslandId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Current snapshot format version
+pub const SNAPSHOT_VERSION: u32 = 2;
+
+/// Error types for snapshot operations
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// Reading or writing the snapshot failed
+    Io(io::Error),
+    /// The snapshot is not valid JSON or does not match the format
+    Json(serde_json::Error),
+    /// The snapshot was written by an incompatible format version
+    UnsupportedVersion(u32),
+}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::Io(e) => write!(f, "snapshot I/O error: {}", e),
+            SnapshotError::Json(e) => write!(f, "invalid snapshot: {}", e),
+            SnapshotError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported snapshot version {} (expected {})",
+                version, SNAPSHOT_VERSION
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SnapshotError::Io(e) => Some(e),
+            SnapshotError::Json(e) => Some(e),
+            SnapshotError::UnsupportedVersion(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for SnapshotError {
+    fn from(e: io::Error) -> Self {
+        SnapshotError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for SnapshotError {
+    fn from(e: serde_json::Error) -> Self {
+        SnapshotError::Json(e)
+    }
+}
+
+/// Serializable copy of a `GraphState` and the stream position it reflects
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphSnapshot {
+    /// Snapshot format version
+    pub version: u32,
+
+    /// Cursor of the last event applied to the state
+    pub cursor: String,
+
+    /// Block number of the last event applied to the state
+    pub block_number: u64,
+
+    /// Known spaces and the topic each announces
+    pub spaces: Vec<SpaceRecord>,
+
+    /// Explicit edges, in insertion order per source
+    pub explicit_edges: Vec<ExplicitEdgeRecord>,
+
+    /// Topic edges
+    pub topic_edges: Vec<TopicEdgeRecord>,
+
+    /// Islands archived to a cold store, which are not in the records above
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive: Option<ArchiveRecord>,
+}
+
+/// A space in a snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpaceRecord {
+    #[serde(with = "hex_id")]
+    pub space_id: SpaceId,
+
+    #[serde(with = "hex_id_opt", default, skip_serializing_if = "Option::is_none")]
+    pub topic_id: Option<TopicId>,
+}
+
+/// An explicit edge in a snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExplicitEdgeRecord {
+    #[serde(with = "hex_id")]
+    pub source: SpaceId,
+
+    #[serde(with = "hex_id")]
+    pub target: SpaceId,
+
+    pub edge_type: SnapshotEdgeType,
+
+    /// Provenance, attached to the first record for each (source, target, type)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<EdgeMetadataRecord>,
+}
+
+/// A topic edge in a snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopicEdgeRecord {
+    #[serde(with = "hex_id")]
+    pub source: SpaceId,
+
+    #[serde(with = "hex_id")]
+    pub topic_id: TopicId,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<EdgeMetadataRecord>,
+}
+
+/// Index of the islands an `IslandPruner` archived to its cold store
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveRecord {
+    /// ID the pruner assigns to the next island it archives
+    pub next_island_id: IslandId,
+
+    /// Archived spaces and the island holding each
+    pub spaces: Vec<ArchivedSpaceRecord>,
+
+    /// Topics announced by archived spaces and the island announcing each
+    pub topics: Vec<ArchivedTopicRecord>,
+}
+
+/// An archived space in a snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivedSpaceRecord {
+    #[serde(with = "hex_id")]
+    pub space_id: SpaceId,
+
+    pub island: IslandId,
+}
+
+/// A topic announced in an archived island
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivedTopicRecord {
+    #[serde(with = "hex_id")]
+    pub topic_id: TopicId,
+
+    pub island: IslandId,
+}
+
+/// Edge provenance in a snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EdgeMetadataRecord {
+    #[serde(with = "hex_id")]
+    pub created_by: SpaceId,
+    pub block_number: u64,
+    pub block_timestamp: u64,
+    pub tx_hash: String,
+}
+
+/// Edge type in a snapshot
+///
+/// Mirrors `EdgeType` so the on-disk format does not change with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotEdgeType {
+    Root,
+    Verified,
+    Related,
+    Topic,
+}
+
+impl From<EdgeType> for SnapshotEdgeType {
+    fn from(edge_type: EdgeType) -> Self {
+        match edge_type {
+            EdgeType::Root => SnapshotEdgeType::Root,
+            EdgeType::Verified => SnapshotEdgeType::Verified,
+            EdgeType::Related => SnapshotEdgeType::Related,
+            EdgeType::Topic => SnapshotEdgeType::Topic,
+        }
+    }
+}
+
+impl From<SnapshotEdgeType> for EdgeType {
+    fn from(edge_type: SnapshotEdgeType) -> Self {
+        match edge_type {
+            SnapshotEdgeType::Root => EdgeType::Root,
+            SnapshotEdgeType::Verified => EdgeType::Verified,
+            SnapshotEdgeType::Related => EdgeType::Related,
+            SnapshotEdgeType::Topic => EdgeType::Topic,
+        }
+    }
+}
+
+impl From<&EdgeMetadata> for EdgeMetadataRecord {
+    fn from(meta: &EdgeMetadata) -> Self {
+        EdgeMetadataRecord {
+            created_by: meta.created_by,
+            block_number: meta.block_number,
+            block_timestamp: meta.block_timestamp,
+            tx_hash: meta.tx_hash.clone(),
+        }
+    }
+}
+
+impl From<&EdgeMetadataRecord> for EdgeMetadata {
+    fn from(record: &EdgeMetadataRecord) -> Self {
+        EdgeMetadata {
+            created_by: record.created_by,
+            block_number: record.block_number,
+            block_timestamp: record.block_timestamp,
+            tx_hash: record.tx_hash.clone(),
+        }
+    }
+}
+
+impl GraphSnapshot {
+    /// Capture a graph state after the event at `cursor` / `block_number`
+    pub fn capture(state: &GraphState, cursor: &str, block_number: u64) -> Self {
+        let mut spaces: Vec<SpaceRecord> = state
+            .spaces
+            .iter()
+            .chain(state.space_topics.keys())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .map(|space_id| SpaceRecord {
+                space_id: *space_id,
+                topic_id: state.space_topics.get(space_id).copied(),
+            })
+            .collect();
+        spaces.sort_by_key(|record| record.space_id);
+
+        let mut sources: Vec<&SpaceId> = state.explicit_edges.keys().collect();
+        sources.sort();
+        let mut explicit_edges = Vec::with_capacity(state.explicit_edge_count());
+        for source in sources {
+            let mut seen = HashSet::new();
+            for (target, edge_type) in &state.explicit_edges[source] {
+                let metadata = if seen.insert((*target, *edge_type)) {
+                    state
+                        .get_explicit_edge_metadata(source, target, *edge_type)
+                        .map(EdgeMetadataRecord::from)
+                } else {
+                    None
+                };
+                explicit_edges.push(ExplicitEdgeRecord {
+                    source: *source,
+                    target: *target,
+                    edge_type: (*edge_type).into(),
+                    metadata,
+                });
+            }
+        }
+
+        let mut topic_edges: Vec<TopicEdgeRecord> = state
+            .topic_edges
+            .iter()
+            .flat_map(|(source, topics)| {
+                topics.iter().map(|topic_id| TopicEdgeRecord {
+                    source: *source,
+                    topic_id: *topic_id,
+                    metadata: state
+                        .get_topic_edge_metadata(source, topic_id)
+                        .map(EdgeMetadataRecord::from),
+                })
+            })
+            .collect();
+        topic_edges.sort_by_key(|record| (record.source, record.topic_id));
+
+        GraphSnapshot {
+            version: SNAPSHOT_VERSION,
+            cursor: cursor.to_string(),
+            block_number,
+            spaces,
+            explicit_edges,
+            topic_edges,
+            archive: None,
+        }
+    }
+
+    /// Record the index of islands archived outside the captured state
+    pub fn with_archive(mut self, archive: ArchiveRecord) -> Self {
+        self.archive = Some(archive);
+        self
+    }
+
+    /// Serialize the snapshot as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String, SnapshotError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Parse a snapshot from JSON, migrating it to the current format version
+    pub fn from_json(json: &str) -> Result<Self, SnapshotError> {
+        Self::from_slice(json.as_bytes())
+    }
+
+    /// Parse a snapshot from JSON bytes, migrating it to the current format version
+    pub(crate) fn from_slice(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        let snapshot: GraphSnapshot = serde_json::from_slice(bytes)?;
+        snapshot.migrate()
+    }
+
+    /// Upgrade a snapshot written by an older format version
+    ///
+    /// Every released version must have an upgrade path here; snapshots from
+    /// a newer Atlas are rejected.
+    fn migrate(self) -> Result<Self, SnapshotError> {
+        match self.version {
+            SNAPSHOT_VERSION => Ok(self),
+            // Version 1 inlined archived islands and has no archive index
+            1 => Ok(GraphSnapshot {
+                version: SNAPSHOT_VERSION,
+                ..self
+            }),
+            version => Err(SnapshotError::UnsupportedVersion(version)),
+        }
+    }
+}
+
+impl GraphState {
+    /// Export this state as a snapshot with no stream position
+    pub fn to_snapshot(&self) -> GraphSnapshot {
+        GraphSnapshot::capture(self, "", 0)
+    }
+
+    /// Import a state from a snapshot, migrating it to the current format version
+    pub fn from_snapshot(snapshot: GraphSnapshot) -> Result<GraphState, SnapshotError> {
+        Ok(GraphState::restore(&snapshot.migrate()?))
+    }
+
+    /// Rebuild a graph state from a snapshot
+    ///
+    /// Reverse indexes (topic members, topic edge sources) are rebuilt from
+    /// the stored records.
+    pub fn restore(snapshot: &GraphSnapshot) -> GraphState {
+        let mut state = GraphState::new();
+
+        for record in &snapshot.spaces {
+            state.spaces.insert(record.space_id);
+            if let Some(topic_id) = record.topic_id {
+                state.space_topics.insert(record.space_id, topic_id);
+                state
+                    .topic_spaces
+                    .entry(topic_id)
+                    .or_default()
+                    .insert(record.space_id);
+            }
+        }
+
+        for record in &snapshot.explicit_edges {
+            let edge_type = EdgeType::from(record.edge_type);
+            state
+                .explicit_edges
+                .entry(record.source)
+                .or_default()
+                .push((record.target, edge_type));
+            if let Some(metadata) = &record.metadata {
+                state
+                    .explicit_edge_metadata
+                    .entry((record.source, record.target, edge_type))
+                    .or_insert_with(|| metadata.into());
+            }
+        }
+
+        for record in &snapshot.topic_edges {
+            state
+                .topic_edges
+                .entry(record.source)
+                .or_default()
+                .insert(record.topic_id);
+            state
+                .topic_edge_sources
+                .entry(record.topic_id)
+                .or_default()
+                .insert(record.source);
+            if let Some(metadata) = &record.metadata {
+                state
+                    .topic_edge_metadata
+                    .insert((record.source, record.topic_id), metadata.into());
+            }
+        }
+
+        state
+    }
+}
+
+/// Durable storage for the latest snapshot
+pub trait SnapshotStore {
+    /// Replace the stored snapshot
+    fn save(&mut self, snapshot: &GraphSnapshot) -> Result<(), SnapshotError>;
+
+    /// Load the stored snapshot, if any
+    fn load(&self) -> Result<Option<GraphSnapshot>, SnapshotError>;
+}
+
+/// Snapshot store backed by a single JSON file
+#[derive(Debug, Clone)]
+pub struct FileSnapshotStore {
+    path: PathBuf,
+}
+
+impl FileSnapshotStore {
+    /// Create a store that reads and writes the given path
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Get the snapshot file path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl SnapshotStore for FileSnapshotStore {
+    fn save(&mut self, snapshot: &GraphSnapshot) -> Result<(), SnapshotError> {
+        write_durably(&self.path, &serde_json::to_vec(snapshot)?)?;
+        Ok(())
+    }
+
+    fn load(&self) -> Result<Option<GraphSnapshot>, SnapshotError> {
+        let bytes = match fs::read(&self.path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        GraphSnapshot::from_slice(&bytes).map(Some)
+    }
+}
+
+/// Atomically replace `path` with `bytes`, surviving a crash or power loss
+///
+/// The data is written to a temporary file and synced before it is renamed
+/// over `path`, and the directory is synced so the rename itself is on disk
+/// when this returns. A reader sees either the old or the new contents.
+pub(crate) fn write_durably(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(dir)?;
+
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+
+    let mut file = File::create(&tmp)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(&tmp, path)?;
+    sync_dir(dir)
+}
+
+/// Flush a directory's entries, so renames in it are durable
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    File::open(dir)?.sync_all()
+}
+
+/// Directories cannot be opened for syncing on this platform
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+/// Serde adapter for 16-byte IDs as hex strings
+mod hex_id {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(id: &[u8; 16], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(id))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 16], D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let mut id = [0u8; 16];
+        hex::decode_to_slice(&s, &mut id).map_err(D::Error::custom)?;
+        Ok(id)
+    }
+}
+
+/// Serde adapter for optional 16-byte IDs as hex strings
+mod hex_id_opt {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        id: &Option<[u8; 16]>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match id {
+            Some(id) => super::hex_id::serialize(id, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<[u8; 16]>, D::Error> {
+        #[derive(Deserialize)]
+        struct Wrapper(#[serde(with = "super::hex_id")] [u8; 16]);
+
+        Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|Wrapper(id)| id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert::convert_mock_blocks;
+    use crate::events::{
+        BlockMetadata, SpaceTopologyEvent, SpaceTopologyPayload, TrustExtended, TrustExtension,
+    };
+    use crate::graph::{CanonicalProcessor, TransitiveProcessor};
+    use mock_substream::test_topology;
+
+    fn topology_state() -> GraphState {
+        let mut state = GraphState::new();
+        for event in convert_mock_blocks(&test_topology::generate()) {
+            state.apply_event(&event);
+        }
+        state
+    }
+
+    fn assert_same_state(a: &GraphState, b: &GraphState) {
+        assert_eq!(a.spaces, b.spaces);
+        assert_eq!(a.space_topics, b.space_topics);
+        assert_eq!(a.topic_spaces, b.topic_spaces);
+        assert_eq!(a.explicit_edges, b.explicit_edges);
+        assert_eq!(a.topic_edges, b.topic_edges);
+        assert_eq!(a.topic_edge_sources, b.topic_edge_sources);
+        assert_eq!(a.explicit_edge_metadata, b.explicit_edge_metadata);
+        assert_eq!(a.topic_edge_metadata, b.topic_edge_metadata);
+    }
+
+    #[test]
+    fn test_capture_restore_round_trip() {
+        let state = topology_state();
+        let snapshot = GraphSnapshot::capture(&state, "cursor_7", 7);
+
+        assert_eq!(snapshot.spaces.len(), 18);
+        assert_eq!(snapshot.explicit_edges.len(), state.explicit_edge_count());
+        assert_same_state(&GraphState::restore(&snapshot), &state);
+    }
+
+    #[test]
+    fn test_restore_then_incremental_update() {
+        let snapshot = GraphSnapshot::capture(&topology_state(), "cursor_7", 7);
+        let mut state = GraphState::restore(&snapshot);
+
+        // Seed the incremental set from the restored state, as Atlas does on startup
+        let mut transitive = TransitiveProcessor::new();
+        let mut canonical = CanonicalProcessor::new(test_topology::ROOT_SPACE_ID);
+        canonical.compute(&state, &mut transitive);
+        assert_eq!(canonical.canonical_set().len(), 11);
+
+        // An edge from a space that was canonical before the restart pulls in island 1
+        let event = SpaceTopologyEvent {
+            meta: BlockMetadata {
+                block_number: 8,
+                block_timestamp: 1_700_000_096,
+                tx_hash: "0x08".to_string(),
+                cursor: "cursor_8".to_string(),
+            },
+            payload: SpaceTopologyPayload::TrustExtended(TrustExtended {
+                source_space_id: test_topology::SPACE_A,
+                extension: TrustExtension::Verified {
+                    target_space_id: test_topology::SPACE_X,
+                },
+            }),
+        };
+        transitive.handle_event(&event, &state);
+        state.apply_event(&event);
+
+        let mut delta = canonical.apply_incremental(&event, &state);
+        delta.added.sort();
+        assert_eq!(
+            delta.added,
+            vec![
+                test_topology::SPACE_X,
+                test_topology::SPACE_Y,
+                test_topology::SPACE_Z,
+                test_topology::SPACE_W,
+            ]
+        );
+        assert!(delta.removed.is_empty());
+    }
+
+    #[test]
+    fn test_capture_is_deterministic() {
+        let a = serde_json::to_string(&GraphSnapshot::capture(&topology_state(), "c", 1)).unwrap();
+        let b = serde_json::to_string(&GraphSnapshot::capture(&topology_state(), "c", 1)).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_fixture_export_import() {
+        let state = topology_state();
+        let json = state.to_snapshot().to_json().unwrap();
+
+        let imported = GraphState::from_snapshot(GraphSnapshot::from_json(&json).unwrap()).unwrap();
+        assert_same_state(&imported, &state);
+
+        let mut future = state.to_snapshot();
+        future.version = SNAPSHOT_VERSION + 1;
+        assert!(matches!(
+            GraphState::from_snapshot(future),
+            Err(SnapshotError::UnsupportedVersion(_))
+        ));
+    }
+
+    #[test]
+    fn test_version_1_migrated() {
+        let mut json: serde_json::Value =
+            serde_json::to_value(topology_state().to_snapshot()).unwrap();
+        json["version"] = 1.into();
+
+        let snapshot = GraphSnapshot::from_json(&json.to_string()).unwrap();
+        assert_eq!(snapshot.version, SNAPSHOT_VERSION);
+        assert_eq!(snapshot.archive, None);
+    }
+
+    #[test]
+    fn test_file_store_round_trip() {
+        let dir = std::env::temp_dir().join(format!("atlas-snapshot-{}", std::process::id()));
+        let mut store = FileSnapshotStore::new(dir.join("state.json"));
+        assert!(store.load().unwrap().is_none());
+
+        let state = topology_state();
+        store
+            .save(&GraphSnapshot::capture(&state, "cursor_9", 9))
+            .unwrap();
+        assert!(!dir.join("state.json.tmp").exists());
+
+        let loaded = store.load().unwrap().unwrap();
+        assert_eq!(loaded.cursor, "cursor_9");
+        assert_eq!(loaded.block_number, 9);
+        assert_same_state(&GraphState::restore(&loaded), &state);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_unsupported_version_rejected() {
+        let dir = std::env::temp_dir().join(format!("atlas-snapshot-v-{}", std::process::id()));
+        let mut store = FileSnapshotStore::new(dir.join("state.json"));
+
+        let mut snapshot = GraphSnapshot::capture(&GraphState::new(), "", 0);
+        snapshot.version = SNAPSHOT_VERSION + 1;
+        store.save(&snapshot).unwrap();
+
+        assert!(matches!(
+            store.load(),
+            Err(SnapshotError::UnsupportedVersion(v)) if v == SNAPSHOT_VERSION + 1
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -3,11 +3,15 @@
 //! Consumes events from mock-substream and transforms them into Hermes protobuf
 //! messages, then publishes to Kafka topics.
 
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use prost::Message;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use rdkafka::config::ClientConfig;
 use rdkafka::message::{Header, OwnedHeaders};
 use rdkafka::producer::{BaseProducer, BaseRecord, Producer};
 use std::env;
+use std::thread;
 use std::time::Duration;
 
 use hermes_schema::pb::blockchain_metadata::BlockchainMetadata;
@@ -16,13 +20,325 @@ use hermes_schema::pb::space::{
     DefaultDaoSpacePayload, HermesCreateSpace, HermesSpaceTrustExtension, PersonalSpacePayload,
     RelatedExtension, SubtopicExtension, VerifiedExtension,
 };
+use hermes_schema::version::{SCHEMA_VERSION, SCHEMA_VERSION_HEADER};
 use wire::pb::grc20::{DataType as WireDataType, Entity, Op, Property, Relation, Value};
 
 use mock_substream::{
-    test_topology, BlockMetadata, EditPublished, MockEvent, SpaceCreated, SpaceType,
-    TrustExtended, TrustExtension,
+    test_topology, BlockMetadata, EditPublished, MockBlock, MockConfig, MockEvent, MockSubstream,
+    SpaceCreated, SpaceType, TrustExtended, TrustExtension,
 };
 
+/// Publishes mock-substream events to Kafka as Hermes protobuf messages
+#[derive(Parser, Debug)]
+#[command(name = "hermes-processor", about)]
+struct Args {
+    /// Scenario to publish (defaults to replay-topology)
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Pace blocks by their original timestamps instead of sending them all at once
+    #[arg(long)]
+    realtime: bool,
+
+    /// Time compression factor for --realtime (e.g. 10 replays 10x faster)
+    #[arg(long, default_value_t = 1.0, requires = "realtime", value_parser = parse_speed)]
+    speed: f64,
+
+    /// Emit at most this many events per second
+    #[arg(long, conflicts_with = "realtime", value_parser = parse_rate)]
+    rate: Option<f64>,
+
+    #[command(flatten)]
+    topics: TopicArgs,
+}
+
+/// Event source to publish
+#[derive(Subcommand, Clone, Debug)]
+enum Command {
+    /// Publish the shared mock-substream test topology
+    ReplayTopology,
+
+    /// Publish a generated topology that is identical for a given seed
+    Deterministic {
+        /// Seed for the generator
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        #[command(flatten)]
+        scenario: ScenarioArgs,
+    },
+
+    /// Publish a generated topology from a fresh random seed
+    Random {
+        #[command(flatten)]
+        scenario: ScenarioArgs,
+    },
+
+    /// Keep publishing generated topologies until stopped
+    Continuous {
+        /// Stop after this many generated topologies
+        #[arg(long)]
+        batches: Option<u64>,
+
+        /// Seed for the generator (random if not set)
+        #[arg(long)]
+        seed: Option<u64>,
+
+        #[command(flatten)]
+        scenario: ScenarioArgs,
+    },
+}
+
+/// Emission rate for `continuous` when --rate is not given
+const DEFAULT_CONTINUOUS_RATE: f64 = 10.0;
+
+/// Shape of a generated topology
+#[derive(clap::Args, Clone, Debug)]
+struct ScenarioArgs {
+    /// Number of spaces to create
+    #[arg(long, default_value_t = 10)]
+    spaces: usize,
+
+    /// Number of edits to publish per space
+    #[arg(long, default_value_t = 0)]
+    edits_per_space: usize,
+
+    /// Multiplier on the chance of each trust edge (1.0 = default density)
+    #[arg(long, default_value_t = 1.0, value_parser = parse_density)]
+    trust_density: f64,
+
+    /// Maximum number of events per block (block sizes are uniform in 1..=N)
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+    max_events_per_block: u64,
+}
+
+impl ScenarioArgs {
+    fn config(&self) -> MockConfig {
+        let mut config = MockConfig::default()
+            .with_num_spaces(self.spaces)
+            .with_edits_per_space(self.edits_per_space)
+            .with_trust_density(self.trust_density)
+            .with_events_per_block(1, self.max_events_per_block as usize);
+        if self.edits_per_space > 0 {
+            config = config.with_edits();
+        }
+        config
+    }
+}
+
+const SPACE_CREATIONS_TOPIC: &str = "space.creations";
+const TRUST_EXTENSIONS_TOPIC: &str = "space.trust.extensions";
+const KNOWLEDGE_EDITS_TOPIC: &str = "knowledge.edits";
+
+/// Message key strategy for space topics
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum SpaceKey {
+    /// The space the event belongs to (creating or extending space)
+    SpaceId,
+    /// The space ID followed by the topic or trust target ID
+    Composite,
+}
+
+/// Message key strategy for the edits topic
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum EditKey {
+    /// The space the edit was published to
+    SpaceId,
+    /// The edit ID
+    EditId,
+    /// `<space_id>:<edit_id>`
+    Composite,
+}
+
+/// Extra static header attached to every message on a topic
+#[derive(Clone, Debug)]
+struct TopicHeader {
+    topic: String,
+    key: String,
+    value: String,
+}
+
+/// Per-topic names, keying and headers
+#[derive(clap::Args, Debug)]
+struct TopicArgs {
+    /// Topic for space creations
+    #[arg(long, default_value = SPACE_CREATIONS_TOPIC)]
+    creations_topic: String,
+
+    /// Topic for trust extensions
+    #[arg(long, default_value = TRUST_EXTENSIONS_TOPIC)]
+    trust_topic: String,
+
+    /// Topic for knowledge edits
+    #[arg(long, default_value = KNOWLEDGE_EDITS_TOPIC)]
+    edits_topic: String,
+
+    /// Key strategy for space creations
+    #[arg(long, value_enum, default_value_t = SpaceKey::SpaceId)]
+    creations_key: SpaceKey,
+
+    /// Key strategy for trust extensions
+    #[arg(long, value_enum, default_value_t = SpaceKey::SpaceId)]
+    trust_key: SpaceKey,
+
+    /// Key strategy for knowledge edits
+    #[arg(long, value_enum, default_value_t = EditKey::SpaceId)]
+    edits_key: EditKey,
+
+    /// Extra header as TOPIC:KEY=VALUE, may be repeated
+    #[arg(long = "header", value_name = "TOPIC:KEY=VALUE", value_parser = parse_topic_header)]
+    headers: Vec<TopicHeader>,
+}
+
+impl TopicArgs {
+    /// Check that every --header names one of the configured topics
+    fn validate(&self) -> Result<(), String> {
+        let topics = [&self.creations_topic, &self.trust_topic, &self.edits_topic];
+        match self.headers.iter().find(|header| !topics.contains(&&header.topic)) {
+            Some(header) => Err(format!("--header names unknown topic: {}", header.topic)),
+            None => Ok(()),
+        }
+    }
+
+    /// Build the headers for a topic, starting from the schema version and its built-in header
+    fn headers_for(&self, topic: &str, key: &str, value: &str) -> OwnedHeaders {
+        let schema_version = SCHEMA_VERSION.to_string();
+        self.headers
+            .iter()
+            .filter(|header| header.topic == topic)
+            .fold(
+                OwnedHeaders::new()
+                    .insert(Header {
+                        key: SCHEMA_VERSION_HEADER,
+                        value: Some(&schema_version),
+                    })
+                    .insert(Header {
+                        key,
+                        value: Some(value),
+                    }),
+                |headers, header| {
+                    headers.insert(Header {
+                        key: &header.key,
+                        value: Some(&header.value),
+                    })
+                },
+            )
+    }
+}
+
+fn parse_topic_header(value: &str) -> Result<TopicHeader, String> {
+    let (topic, header) = value
+        .split_once(':')
+        .ok_or_else(|| format!("expected TOPIC:KEY=VALUE, got {}", value))?;
+    let (key, header_value) = header
+        .split_once('=')
+        .ok_or_else(|| format!("expected TOPIC:KEY=VALUE, got {}", value))?;
+
+    if topic.is_empty() {
+        return Err("header topic must not be empty".to_string());
+    }
+    if key.is_empty() {
+        return Err("header key must not be empty".to_string());
+    }
+
+    Ok(TopicHeader {
+        topic: topic.to_string(),
+        key: key.to_string(),
+        value: header_value.to_string(),
+    })
+}
+
+fn parse_speed(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(speed) if speed.is_finite() && speed > 0.0 => Ok(speed),
+        _ => Err(format!("speed must be a positive number, got {}", value)),
+    }
+}
+
+fn parse_rate(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(rate) if rate.is_finite() && rate > 0.0 => Ok(rate),
+        _ => Err(format!("rate must be a positive number, got {}", value)),
+    }
+}
+
+fn parse_density(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(density) if density.is_finite() && density >= 0.0 => Ok(density),
+        _ => Err(format!("trust density must be a non-negative number, got {}", value)),
+    }
+}
+
+// =============================================================================
+// Pacing
+// =============================================================================
+
+/// Delays inserted between blocks
+enum Pacer {
+    Realtime(ReplayPacer),
+    Rate(RateLimiter),
+}
+
+impl Pacer {
+    /// Time to wait before emitting the given block
+    fn delay_before(&mut self, block: &MockBlock) -> Duration {
+        match self {
+            Pacer::Realtime(pacer) => pacer.delay_for(block.timestamp),
+            Pacer::Rate(limiter) => limiter.delay_before(block.events.len()),
+        }
+    }
+}
+
+/// Caps the number of events emitted per second
+struct RateLimiter {
+    rate: f64,
+    pending_events: usize,
+}
+
+impl RateLimiter {
+    fn new(rate: f64) -> Self {
+        Self {
+            rate,
+            pending_events: 0,
+        }
+    }
+
+    /// Time owed for the previous block before emitting one with `events` events
+    fn delay_before(&mut self, events: usize) -> Duration {
+        let owed = self.pending_events;
+        self.pending_events = events;
+        Duration::from_secs_f64(owed as f64 / self.rate)
+    }
+}
+
+/// Spaces out blocks by the gaps between their original timestamps
+struct ReplayPacer {
+    speed: f64,
+    last_timestamp: Option<u64>,
+}
+
+impl ReplayPacer {
+    fn new(speed: f64) -> Self {
+        Self {
+            speed,
+            last_timestamp: None,
+        }
+    }
+
+    /// Time to wait before emitting a block with the given timestamp
+    ///
+    /// A block timestamped before the previous one is emitted immediately and
+    /// does not move the replay clock back.
+    fn delay_for(&mut self, timestamp: u64) -> Duration {
+        let (gap, latest) = match self.last_timestamp {
+            Some(last) => (timestamp.saturating_sub(last), last.max(timestamp)),
+            None => (0, timestamp),
+        };
+        self.last_timestamp = Some(latest);
+        Duration::from_secs_f64(gap as f64 / self.speed)
+    }
+}
+
 // =============================================================================
 // Conversion: mock-substream -> Hermes protos
 // =============================================================================
@@ -234,12 +550,33 @@ fn create_producer(broker: &str) -> Result<BaseProducer, Box<dyn std::error::Err
 
 fn send_space(
     producer: &BaseProducer,
+    topics: &TopicArgs,
     space: &HermesCreateSpace,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let mut payload = Vec::new();
     space.encode(&mut payload)?;
 
-    let space_type = match &space.payload {
+    let key = space_created_key(topics.creations_key, space);
+    let record = BaseRecord::to(&topics.creations_topic)
+        .key(&key)
+        .payload(&payload)
+        .headers(topics.headers_for(&topics.creations_topic, "space-type", space_type(space)));
+
+    producer.send(record).map_err(|(e, _)| e)?;
+    Ok(())
+}
+
+/// Message key for a space creation
+fn space_created_key(strategy: SpaceKey, space: &HermesCreateSpace) -> Vec<u8> {
+    match strategy {
+        SpaceKey::SpaceId => space.space_id.clone(),
+        SpaceKey::Composite => [space.space_id.as_slice(), &space.topic_id].concat(),
+    }
+}
+
+/// Value of the `space-type` header for a space creation
+fn space_type(space: &HermesCreateSpace) -> &'static str {
+    match &space.payload {
         Some(hermes_schema::pb::space::hermes_create_space::Payload::PersonalSpace(_)) => {
             "PERSONAL"
         }
@@ -247,28 +584,52 @@ fn send_space(
             "DEFAULT_DAO"
         }
         None => "UNKNOWN",
-    };
-
-    let record = BaseRecord::to("space.creations")
-        .key(&space.space_id)
-        .payload(&payload)
-        .headers(OwnedHeaders::new().insert(Header {
-            key: "space-type",
-            value: Some(space_type),
-        }));
-
-    producer.send(record).map_err(|(e, _)| e)?;
-    Ok(())
+    }
 }
 
 fn send_trust_extension(
     producer: &BaseProducer,
+    topics: &TopicArgs,
     trust_extension: &HermesSpaceTrustExtension,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let mut payload = Vec::new();
     trust_extension.encode(&mut payload)?;
 
-    let extension_type = match &trust_extension.extension {
+    let key = trust_extension_key(topics.trust_key, trust_extension);
+    let record = BaseRecord::to(&topics.trust_topic)
+        .key(&key)
+        .payload(&payload)
+        .headers(topics.headers_for(
+            &topics.trust_topic,
+            "extension-type",
+            extension_type(trust_extension),
+        ));
+
+    producer.send(record).map_err(|(e, _)| e)?;
+    Ok(())
+}
+
+/// Message key for a trust extension
+fn trust_extension_key(strategy: SpaceKey, trust_extension: &HermesSpaceTrustExtension) -> Vec<u8> {
+    match strategy {
+        SpaceKey::SpaceId => trust_extension.source_space_id.clone(),
+        SpaceKey::Composite => {
+            use hermes_schema::pb::space::hermes_space_trust_extension::Extension;
+
+            let target: &[u8] = match &trust_extension.extension {
+                Some(Extension::Verified(verified)) => &verified.target_space_id,
+                Some(Extension::Related(related)) => &related.target_space_id,
+                Some(Extension::Subtopic(subtopic)) => &subtopic.target_topic_id,
+                None => &[],
+            };
+            [trust_extension.source_space_id.as_slice(), target].concat()
+        }
+    }
+}
+
+/// Value of the `extension-type` header for a trust extension
+fn extension_type(trust_extension: &HermesSpaceTrustExtension) -> &'static str {
+    match &trust_extension.extension {
         Some(hermes_schema::pb::space::hermes_space_trust_extension::Extension::Verified(_)) => {
             "VERIFIED"
         }
@@ -279,44 +640,146 @@ fn send_trust_extension(
             "SUBTOPIC"
         }
         None => "UNKNOWN",
-    };
-
-    let record = BaseRecord::to("space.trust.extensions")
-        .key(&trust_extension.source_space_id)
-        .payload(&payload)
-        .headers(OwnedHeaders::new().insert(Header {
-            key: "extension-type",
-            value: Some(extension_type),
-        }));
-
-    producer.send(record).map_err(|(e, _)| e)?;
-    Ok(())
+    }
 }
 
 fn send_edit(
     producer: &BaseProducer,
+    topics: &TopicArgs,
     edit: &HermesEdit,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let mut payload = Vec::new();
     edit.encode(&mut payload)?;
 
-    let record = BaseRecord::to("knowledge.edits")
-        .key(&edit.space_id)
+    let key = edit_key(topics.edits_key, edit);
+    let record = BaseRecord::to(&topics.edits_topic)
+        .key(&key)
         .payload(&payload)
-        .headers(OwnedHeaders::new().insert(Header {
-            key: "edit-name",
-            value: Some(&edit.name),
-        }));
+        .headers(topics.headers_for(&topics.edits_topic, "edit-name", &edit.name));
 
     producer.send(record).map_err(|(e, _)| e)?;
     Ok(())
 }
 
+/// Message key for a knowledge edit
+fn edit_key(strategy: EditKey, edit: &HermesEdit) -> Vec<u8> {
+    match strategy {
+        EditKey::SpaceId => edit.space_id.as_bytes().to_vec(),
+        EditKey::EditId => edit.id.clone(),
+        EditKey::Composite => format!("{}:{}", edit.space_id, hex::encode(&edit.id)).into_bytes(),
+    }
+}
+
+// =============================================================================
+// Publishing
+// =============================================================================
+
+/// Counts of published and dropped events
+#[derive(Default)]
+struct PublishStats {
+    spaces: u64,
+    trust_extensions: u64,
+    edits: u64,
+    skipped: u64,
+    errors: u64,
+}
+
+/// Sends blocks to Kafka with optional pacing
+struct Publisher<'a> {
+    producer: &'a BaseProducer,
+    topics: &'a TopicArgs,
+    pacer: Option<Pacer>,
+    stats: PublishStats,
+}
+
+impl Publisher<'_> {
+    fn publish_blocks(&mut self, blocks: &[MockBlock]) -> Result<(), Box<dyn std::error::Error>> {
+        for block in blocks {
+            if let Some(pacer) = self.pacer.as_mut() {
+                let delay = pacer.delay_before(block);
+                if !delay.is_zero() {
+                    // Deliver what has been queued so far before waiting
+                    self.producer.flush(Duration::from_secs(30))?;
+                    thread::sleep(delay);
+                }
+            }
+
+            for event in &block.events {
+                if let Err(e) = self.publish_event(event) {
+                    eprintln!("Error processing event: {}", e);
+                    self.stats.errors += 1;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn publish_event(&mut self, event: &MockEvent) -> Result<(), Box<dyn std::error::Error>> {
+        match event {
+            MockEvent::SpaceCreated(space) => {
+                let hermes_space = convert_space_created(space);
+                send_space(self.producer, self.topics, &hermes_space)?;
+                self.stats.spaces += 1;
+                println!("Space created: {}", hex::encode(space.space_id));
+            }
+            MockEvent::TrustExtended(trust) => {
+                let hermes_trust = convert_trust_extended(trust);
+                let ext_type = match &trust.extension {
+                    TrustExtension::Verified { .. } => "verified",
+                    TrustExtension::Related { .. } => "related",
+                    TrustExtension::Subtopic { .. } => "subtopic",
+                };
+                send_trust_extension(self.producer, self.topics, &hermes_trust)?;
+                self.stats.trust_extensions += 1;
+                println!(
+                    "Trust extended: {} -> {} ({})",
+                    hex::encode(trust.source_space_id),
+                    ext_type,
+                    ext_type
+                );
+            }
+            MockEvent::EditPublished(edit) => {
+                let hermes_edit = convert_edit_published(edit);
+                send_edit(self.producer, self.topics, &hermes_edit)?;
+                self.stats.edits += 1;
+                println!(
+                    "Edit published: {} in space {} ({} ops)",
+                    edit.name,
+                    hex::encode(edit.space_id),
+                    edit.ops.len()
+                );
+            }
+            MockEvent::MemberAdded(_) | MockEvent::MemberRemoved(_) | MockEvent::EditorAdded(_) => {
+                // No Hermes message for DAO governance yet
+                self.stats.skipped += 1;
+            }
+            MockEvent::UndoSignal(undo) => {
+                // Hermes has no undo message yet; consumers see the replayed blocks
+                println!(
+                    "Undo signal: rolled back to block {} ({})",
+                    undo.last_valid_block, undo.cursor
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
 // =============================================================================
 // Main
 // =============================================================================
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    if let Err(message) = args.topics.validate() {
+        Args::command()
+            .error(clap::error::ErrorKind::ValueValidation, message)
+            .exit();
+    }
+
+    let command = args.command.clone().unwrap_or(Command::ReplayTopology);
     let broker = env::var("KAFKA_BROKER").unwrap_or_else(|_| "localhost:9092".to_string());
 
     println!("Hermes Processor starting...");
@@ -326,69 +789,65 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     println!("Connected to Kafka broker");
 
-    // Generate deterministic topology from mock-substream
-    println!("\n=== Processing mock-substream topology ===\n");
-    let blocks = test_topology::generate();
-
-    let mut space_count = 0;
-    let mut trust_count = 0;
-    let mut edit_count = 0;
-    let mut error_count = 0;
-
-    for block in &blocks {
-        for event in &block.events {
-            let result = match event {
-                MockEvent::SpaceCreated(space) => {
-                    let hermes_space = convert_space_created(space);
-                    let space_id_hex = hex::encode(&space.space_id);
-                    match send_space(&producer, &hermes_space) {
-                        Ok(_) => {
-                            space_count += 1;
-                            println!("Space created: {}", space_id_hex);
-                            Ok(())
-                        }
-                        Err(e) => Err(e),
-                    }
-                }
-                MockEvent::TrustExtended(trust) => {
-                    let hermes_trust = convert_trust_extended(trust);
-                    let source_hex = hex::encode(&trust.source_space_id);
-                    let ext_type = match &trust.extension {
-                        TrustExtension::Verified { .. } => "verified",
-                        TrustExtension::Related { .. } => "related",
-                        TrustExtension::Subtopic { .. } => "subtopic",
-                    };
-                    match send_trust_extension(&producer, &hermes_trust) {
-                        Ok(_) => {
-                            trust_count += 1;
-                            println!("Trust extended: {} -> {} ({})", source_hex, ext_type, ext_type);
-                            Ok(())
-                        }
-                        Err(e) => Err(e),
-                    }
-                }
-                MockEvent::EditPublished(edit) => {
-                    let hermes_edit = convert_edit_published(edit);
-                    let space_id_hex = hex::encode(&edit.space_id);
-                    match send_edit(&producer, &hermes_edit) {
-                        Ok(_) => {
-                            edit_count += 1;
-                            println!(
-                                "Edit published: {} in space {} ({} ops)",
-                                edit.name,
-                                space_id_hex,
-                                edit.ops.len()
-                            );
-                            Ok(())
-                        }
-                        Err(e) => Err(e),
-                    }
-                }
-            };
+    let rate = match command {
+        Command::Continuous { .. } if !args.realtime => {
+            Some(args.rate.unwrap_or(DEFAULT_CONTINUOUS_RATE))
+        }
+        _ => args.rate,
+    };
+    let pacer = if args.realtime {
+        println!("Realtime replay at {}x speed", args.speed);
+        Some(Pacer::Realtime(ReplayPacer::new(args.speed)))
+    } else if let Some(rate) = rate {
+        println!("Emitting at most {} events/s", rate);
+        Some(Pacer::Rate(RateLimiter::new(rate)))
+    } else {
+        None
+    };
 
-            if let Err(e) = result {
-                eprintln!("Error processing event: {}", e);
-                error_count += 1;
+    let mut publisher = Publisher {
+        producer: &producer,
+        topics: &args.topics,
+        pacer,
+        stats: PublishStats::default(),
+    };
+
+    match command {
+        Command::ReplayTopology => {
+            println!("\n=== Processing mock-substream topology ===\n");
+            publisher.publish_blocks(&test_topology::generate())?;
+        }
+        Command::Deterministic { seed, scenario } => {
+            println!("\n=== Processing generated topology (seed {}) ===\n", seed);
+            let mut mock = MockSubstream::new(scenario.config());
+            let blocks = mock.generate_random_topology(&mut StdRng::seed_from_u64(seed));
+            publisher.publish_blocks(&blocks)?;
+        }
+        Command::Random { scenario } => {
+            let seed = rand::random();
+            println!(
+                "\n=== Processing random topology (reproduce with `deterministic --seed {}`) ===\n",
+                seed
+            );
+            let mut mock = MockSubstream::new(scenario.config());
+            let blocks = mock.generate_random_topology(&mut StdRng::seed_from_u64(seed));
+            publisher.publish_blocks(&blocks)?;
+        }
+        Command::Continuous {
+            batches,
+            seed,
+            scenario,
+        } => {
+            let seed = seed.unwrap_or_else(rand::random);
+            println!("\n=== Processing continuous topology (seed {}) ===\n", seed);
+            // One generator keeps block numbers increasing across batches
+            let mut mock = MockSubstream::new(scenario.config());
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut batch = 0;
+            while batches.is_none_or(|limit| batch < limit) {
+                let blocks = mock.generate_random_topology(&mut rng);
+                publisher.publish_blocks(&blocks)?;
+                batch += 1;
             }
         }
     }
@@ -397,12 +856,402 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("\nFlushing messages to Kafka...");
     producer.flush(Duration::from_secs(30))?;
 
+    let stats = publisher.stats;
     println!("\n=== Processing complete ===");
-    println!("Spaces created: {}", space_count);
-    println!("Trust extensions: {}", trust_count);
-    println!("Edits published: {}", edit_count);
-    println!("Errors: {}", error_count);
+    println!("Spaces created: {}", stats.spaces);
+    println!("Trust extensions: {}", stats.trust_extensions);
+    println!("Edits published: {}", stats.edits);
+    println!("Skipped (no Hermes message): {}", stats.skipped);
+    println!("Errors: {}", stats.errors);
     println!("\nHermes Processor finished.");
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rdkafka::message::Headers;
+
+    fn parse(args: &[&str]) -> Result<Args, clap::Error> {
+        Args::try_parse_from(std::iter::once("hermes-processor").chain(args.iter().copied()))
+    }
+
+    fn header_pairs(headers: &OwnedHeaders) -> Vec<(String, String)> {
+        headers
+            .iter()
+            .map(|header| {
+                (
+                    header.key.to_string(),
+                    String::from_utf8(header.value.unwrap_or_default().to_vec()).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_defaults() {
+        let args = parse(&[]).unwrap();
+        assert!(args.command.is_none());
+        assert!(!args.realtime);
+        assert_eq!(args.speed, 1.0);
+        assert_eq!(args.rate, None);
+        assert_eq!(args.topics.creations_topic, SPACE_CREATIONS_TOPIC);
+        assert_eq!(args.topics.trust_topic, TRUST_EXTENSIONS_TOPIC);
+        assert_eq!(args.topics.edits_topic, KNOWLEDGE_EDITS_TOPIC);
+        assert_eq!(args.topics.creations_key, SpaceKey::SpaceId);
+        assert_eq!(args.topics.trust_key, SpaceKey::SpaceId);
+        assert_eq!(args.topics.edits_key, EditKey::SpaceId);
+        assert!(args.topics.headers.is_empty());
+    }
+
+    #[test]
+    fn test_parse_subcommands() {
+        let args = parse(&["replay-topology"]).unwrap();
+        assert!(matches!(args.command, Some(Command::ReplayTopology)));
+
+        let args = parse(&[
+            "deterministic",
+            "--seed",
+            "7",
+            "--spaces",
+            "20",
+            "--edits-per-space",
+            "2",
+            "--trust-density",
+            "0.5",
+            "--max-events-per-block",
+            "4",
+        ])
+        .unwrap();
+        let Some(Command::Deterministic { seed, scenario }) = args.command else {
+            panic!("expected deterministic");
+        };
+        assert_eq!(seed, 7);
+        assert_eq!(scenario.spaces, 20);
+        assert_eq!(scenario.edits_per_space, 2);
+        assert_eq!(scenario.trust_density, 0.5);
+        assert_eq!(scenario.max_events_per_block, 4);
+
+        let args = parse(&["random"]).unwrap();
+        let Some(Command::Random { scenario }) = args.command else {
+            panic!("expected random");
+        };
+        assert_eq!(scenario.spaces, 10);
+        assert_eq!(scenario.edits_per_space, 0);
+        assert_eq!(scenario.trust_density, 1.0);
+        assert_eq!(scenario.max_events_per_block, 1);
+
+        let args = parse(&["continuous", "--batches", "3"]).unwrap();
+        let Some(Command::Continuous { batches, seed, .. }) = args.command else {
+            panic!("expected continuous");
+        };
+        assert_eq!(batches, Some(3));
+        assert_eq!(seed, None);
+    }
+
+    #[test]
+    fn test_parse_pacing() {
+        let args = parse(&["--realtime", "--speed", "10", "replay-topology"]).unwrap();
+        assert!(args.realtime);
+        assert_eq!(args.speed, 10.0);
+
+        let args = parse(&["--rate", "2.5", "random"]).unwrap();
+        assert_eq!(args.rate, Some(2.5));
+
+        // --speed only applies to --realtime, which conflicts with --rate
+        assert!(parse(&["--speed", "2"]).is_err());
+        assert!(parse(&["--realtime", "--rate", "5"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_values() {
+        for speed in ["0", "-1", "nan", "inf", "fast"] {
+            assert!(
+                parse(&["--realtime", "--speed", speed]).is_err(),
+                "speed {}",
+                speed
+            );
+        }
+        for rate in ["0", "-2", "nan", "many"] {
+            assert!(parse(&["--rate", rate]).is_err(), "rate {}", rate);
+        }
+        assert!(parse(&["random", "--trust-density", "-0.1"]).is_err());
+        assert!(parse(&["random", "--max-events-per-block", "0"]).is_err());
+        assert!(parse(&["--creations-key", "edit-id"]).is_err());
+        assert!(parse(&["--header", "space.creations"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_topic_header() {
+        let header = parse_topic_header("space.creations:source=mock=v2").unwrap();
+        assert_eq!(header.topic, "space.creations");
+        assert_eq!(header.key, "source");
+        assert_eq!(header.value, "mock=v2");
+
+        // Empty values are allowed, empty topics and keys are not
+        assert_eq!(parse_topic_header("t:k=").unwrap().value, "");
+        assert!(parse_topic_header(":k=v").is_err());
+        assert!(parse_topic_header("t:=v").is_err());
+        assert!(parse_topic_header("t:k").is_err());
+        assert!(parse_topic_header("k=v").is_err());
+    }
+
+    #[test]
+    fn test_validate_header_topics() {
+        let args = parse(&["--header", "space.creations:env=test"]).unwrap();
+        assert!(args.topics.validate().is_ok());
+
+        // Headers follow renamed topics
+        let args = parse(&["--trust-topic", "trust.v2", "--header", "trust.v2:env=test"]).unwrap();
+        assert!(args.topics.validate().is_ok());
+
+        let args = parse(&[
+            "--header",
+            "space.trust.extensions:env=test",
+            "--trust-topic",
+            "trust.v2",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.topics.validate(),
+            Err("--header names unknown topic: space.trust.extensions".to_string())
+        );
+    }
+
+    #[test]
+    fn test_headers_for() {
+        let args = parse(&[
+            "--header",
+            "space.creations:env=test",
+            "--header",
+            "knowledge.edits:env=other",
+            "--header",
+            "space.creations:team=atlas",
+        ])
+        .unwrap();
+
+        let headers = args
+            .topics
+            .headers_for(SPACE_CREATIONS_TOPIC, "space-type", "PERSONAL");
+        assert_eq!(
+            header_pairs(&headers),
+            vec![
+                (
+                    SCHEMA_VERSION_HEADER.to_string(),
+                    SCHEMA_VERSION.to_string()
+                ),
+                ("space-type".to_string(), "PERSONAL".to_string()),
+                ("env".to_string(), "test".to_string()),
+                ("team".to_string(), "atlas".to_string()),
+            ]
+        );
+
+        let headers = args
+            .topics
+            .headers_for(TRUST_EXTENSIONS_TOPIC, "extension-type", "VERIFIED");
+        assert_eq!(
+            header_pairs(&headers),
+            vec![
+                (
+                    SCHEMA_VERSION_HEADER.to_string(),
+                    SCHEMA_VERSION.to_string()
+                ),
+                ("extension-type".to_string(), "VERIFIED".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_space_created_key() {
+        use hermes_schema::pb::space::hermes_create_space::Payload;
+
+        let personal = HermesCreateSpace {
+            space_id: vec![1, 2],
+            topic_id: vec![3, 4],
+            meta: None,
+            payload: Some(Payload::PersonalSpace(PersonalSpacePayload {
+                owner: vec![9],
+            })),
+        };
+        assert_eq!(space_created_key(SpaceKey::SpaceId, &personal), vec![1, 2]);
+        assert_eq!(
+            space_created_key(SpaceKey::Composite, &personal),
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(space_type(&personal), "PERSONAL");
+
+        let dao = HermesCreateSpace {
+            payload: Some(Payload::DefaultDaoSpace(DefaultDaoSpacePayload {
+                initial_editors: vec![vec![9]],
+                initial_members: vec![],
+            })),
+            ..personal.clone()
+        };
+        assert_eq!(space_created_key(SpaceKey::SpaceId, &dao), vec![1, 2]);
+        assert_eq!(
+            space_created_key(SpaceKey::Composite, &dao),
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(space_type(&dao), "DEFAULT_DAO");
+
+        let unknown = HermesCreateSpace {
+            payload: None,
+            ..personal
+        };
+        assert_eq!(space_type(&unknown), "UNKNOWN");
+    }
+
+    #[test]
+    fn test_trust_extension_key() {
+        use hermes_schema::pb::space::hermes_space_trust_extension::Extension;
+
+        let extension = |extension| HermesSpaceTrustExtension {
+            source_space_id: vec![1, 2],
+            meta: None,
+            extension,
+        };
+
+        let verified = extension(Some(Extension::Verified(VerifiedExtension {
+            target_space_id: vec![3],
+        })));
+        assert_eq!(
+            trust_extension_key(SpaceKey::SpaceId, &verified),
+            vec![1, 2]
+        );
+        assert_eq!(
+            trust_extension_key(SpaceKey::Composite, &verified),
+            vec![1, 2, 3]
+        );
+        assert_eq!(extension_type(&verified), "VERIFIED");
+
+        let related = extension(Some(Extension::Related(RelatedExtension {
+            target_space_id: vec![4],
+        })));
+        assert_eq!(trust_extension_key(SpaceKey::SpaceId, &related), vec![1, 2]);
+        assert_eq!(
+            trust_extension_key(SpaceKey::Composite, &related),
+            vec![1, 2, 4]
+        );
+        assert_eq!(extension_type(&related), "RELATED");
+
+        let subtopic = extension(Some(Extension::Subtopic(SubtopicExtension {
+            target_topic_id: vec![5],
+        })));
+        assert_eq!(
+            trust_extension_key(SpaceKey::SpaceId, &subtopic),
+            vec![1, 2]
+        );
+        assert_eq!(
+            trust_extension_key(SpaceKey::Composite, &subtopic),
+            vec![1, 2, 5]
+        );
+        assert_eq!(extension_type(&subtopic), "SUBTOPIC");
+
+        // Without an extension the composite key is just the source space
+        let unknown = extension(None);
+        assert_eq!(
+            trust_extension_key(SpaceKey::Composite, &unknown),
+            vec![1, 2]
+        );
+        assert_eq!(extension_type(&unknown), "UNKNOWN");
+    }
+
+    #[test]
+    fn test_edit_key() {
+        let edit = HermesEdit {
+            id: vec![0xab, 0xcd],
+            name: "Add cities".to_string(),
+            space_id: "space-1".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(edit_key(EditKey::SpaceId, &edit), b"space-1".to_vec());
+        assert_eq!(edit_key(EditKey::EditId, &edit), vec![0xab, 0xcd]);
+        assert_eq!(
+            edit_key(EditKey::Composite, &edit),
+            b"space-1:abcd".to_vec()
+        );
+    }
+
+    #[test]
+    fn test_edit_headers() {
+        let args = parse(&["--header", "knowledge.edits:env=test"]).unwrap();
+
+        let headers = args
+            .topics
+            .headers_for(KNOWLEDGE_EDITS_TOPIC, "edit-name", "Add cities");
+        assert_eq!(
+            header_pairs(&headers),
+            vec![
+                (
+                    SCHEMA_VERSION_HEADER.to_string(),
+                    SCHEMA_VERSION.to_string()
+                ),
+                ("edit-name".to_string(), "Add cities".to_string()),
+                ("env".to_string(), "test".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rate_limiter_delay() {
+        let mut limiter = RateLimiter::new(10.0);
+
+        // Nothing is owed before the first block
+        assert_eq!(limiter.delay_before(5), Duration::ZERO);
+        // Five events at 10/s are paid for before the next block
+        assert_eq!(limiter.delay_before(0), Duration::from_millis(500));
+        // An empty block owes nothing
+        assert_eq!(limiter.delay_before(1), Duration::ZERO);
+
+        let mut limiter = RateLimiter::new(0.5);
+        limiter.delay_before(1);
+        assert_eq!(limiter.delay_before(1), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_replay_pacer_delay() {
+        let mut pacer = ReplayPacer::new(1.0);
+
+        // The first block is emitted immediately
+        assert_eq!(pacer.delay_for(1_700_000_000), Duration::ZERO);
+        assert_eq!(pacer.delay_for(1_700_000_012), Duration::from_secs(12));
+
+        // Zero and negative gaps do not wait or rewind the replay clock
+        assert_eq!(pacer.delay_for(1_700_000_012), Duration::ZERO);
+        assert_eq!(pacer.delay_for(1_700_000_005), Duration::ZERO);
+        assert_eq!(pacer.delay_for(1_700_000_024), Duration::from_secs(12));
+    }
+
+    #[test]
+    fn test_replay_pacer_speed() {
+        let mut pacer = ReplayPacer::new(4.0);
+        pacer.delay_for(0);
+        assert_eq!(pacer.delay_for(12), Duration::from_secs(3));
+
+        let mut pacer = ReplayPacer::new(0.5);
+        pacer.delay_for(0);
+        assert_eq!(pacer.delay_for(12), Duration::from_secs(24));
+    }
+
+    #[test]
+    fn test_pacer_uses_block_shape() {
+        let blocks = test_topology::generate();
+        let (first, second) = (&blocks[0], &blocks[1]);
+
+        let mut pacer = Pacer::Realtime(ReplayPacer::new(1.0));
+        assert_eq!(pacer.delay_before(first), Duration::ZERO);
+        assert_eq!(
+            pacer.delay_before(second),
+            Duration::from_secs(second.timestamp - first.timestamp)
+        );
+
+        let mut pacer = Pacer::Rate(RateLimiter::new(1.0));
+        assert_eq!(pacer.delay_before(first), Duration::ZERO);
+        assert_eq!(
+            pacer.delay_before(second),
+            Duration::from_secs(first.events.len() as u64)
+        );
+    }
+}
@@ -19,6 +19,29 @@ pub struct CanonicalGraphUpdated {
     /// Block metadata from the event that triggered this update
     #[prost(message, optional, tag = "4")]
     pub meta: ::core::option::Option<super::blockchain_metadata::BlockchainMetadata>,
+    /// Trust distance and path for each canonical space, sorted by space ID.
+    /// Lets downstream rankers weight spaces by trust without walking the tree.
+    #[prost(message, repeated, tag = "5")]
+    pub space_trust: ::prost::alloc::vec::Vec<SpaceTrust>,
+}
+/// Emitted in incremental mode when the canonical set changes.
+/// Carries only the spaces that joined or left the canonical set. Consumers
+/// resync from the periodic CanonicalGraphUpdated snapshots and apply diffs
+/// with a later block number on top.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CanonicalGraphDiff {
+    /// Root space the canonical set is computed from
+    #[prost(bytes = "vec", tag = "1")]
+    pub root_id: ::prost::alloc::vec::Vec<u8>,
+    /// Spaces that joined the canonical set
+    #[prost(bytes = "vec", repeated, tag = "2")]
+    pub added_space_ids: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    /// Spaces that left the canonical set
+    #[prost(bytes = "vec", repeated, tag = "3")]
+    pub removed_space_ids: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    /// Block metadata from the event that caused the change
+    #[prost(message, optional, tag = "4")]
+    pub meta: ::core::option::Option<super::blockchain_metadata::BlockchainMetadata>,
 }
 /// A node in the canonical tree.
 /// Represents a space and how it was reached from its parent.
@@ -55,6 +78,25 @@ pub mod canonical_tree_node {
         Topic(super::TopicEdge),
     }
 }
+/// How a canonical space is reached from the root.
+/// When a space appears more than once in the tree, the occurrence with the
+/// strongest path type is used, preferring the shallowest at equal strength.
+/// depth and parent_space_id describe that same occurrence.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SpaceTrust {
+    /// The canonical space
+    #[prost(bytes = "vec", tag = "1")]
+    pub space_id: ::prost::alloc::vec::Vec<u8>,
+    /// Number of edges between the root and this space (0 for the root)
+    #[prost(uint32, tag = "2")]
+    pub depth: u32,
+    /// Space this one is reached from; empty for the root
+    #[prost(bytes = "vec", tag = "3")]
+    pub parent_space_id: ::prost::alloc::vec::Vec<u8>,
+    /// Weakest edge type along the path from the root
+    #[prost(enumeration = "TrustPathType", tag = "4")]
+    pub path_type: i32,
+}
 /// Root node edge - no additional data needed
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct RootEdge {}
@@ -71,3 +113,63 @@ pub struct TopicEdge {
     #[prost(bytes = "vec", tag = "1")]
     pub topic_id: ::prost::alloc::vec::Vec<u8>,
 }
+/// Emitted when a space creates trust edges at an abnormal rate.
+/// Used as an early warning for trust-graph spam.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TrustRateAnomaly {
+    /// The space extending trust at an abnormal rate
+    #[prost(bytes = "vec", tag = "1")]
+    pub space_id: ::prost::alloc::vec::Vec<u8>,
+    /// Number of trust edges created by the space within the window
+    #[prost(uint64, tag = "2")]
+    pub edge_count: u64,
+    /// Length of the sliding window in seconds
+    #[prost(uint64, tag = "3")]
+    pub window_seconds: u64,
+    /// Maximum number of edges allowed within the window
+    #[prost(uint64, tag = "4")]
+    pub threshold: u64,
+    /// Block metadata from the event that crossed the threshold
+    #[prost(message, optional, tag = "5")]
+    pub meta: ::core::option::Option<super::blockchain_metadata::BlockchainMetadata>,
+}
+/// Strength of a trust path, from strongest to weakest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TrustPathType {
+    Unspecified = 0,
+    /// The root space itself
+    Root = 1,
+    /// Every edge on the path is verified
+    Verified = 2,
+    /// The path includes at least one related edge, but no topic edges
+    Related = 3,
+    /// The path includes at least one topic edge
+    Topic = 4,
+}
+impl TrustPathType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "TRUST_PATH_TYPE_UNSPECIFIED",
+            Self::Root => "TRUST_PATH_TYPE_ROOT",
+            Self::Verified => "TRUST_PATH_TYPE_VERIFIED",
+            Self::Related => "TRUST_PATH_TYPE_RELATED",
+            Self::Topic => "TRUST_PATH_TYPE_TOPIC",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "TRUST_PATH_TYPE_UNSPECIFIED" => Some(Self::Unspecified),
+            "TRUST_PATH_TYPE_ROOT" => Some(Self::Root),
+            "TRUST_PATH_TYPE_VERIFIED" => Some(Self::Verified),
+            "TRUST_PATH_TYPE_RELATED" => Some(Self::Related),
+            "TRUST_PATH_TYPE_TOPIC" => Some(Self::Topic),
+            _ => None,
+        }
+    }
+}
@@ -62,6 +62,14 @@ pub enum MockEvent {
     TrustExtended(TrustExtended),
     /// An edit was published to a space.
     EditPublished(EditPublished),
+    /// A member was added to a DAO space.
+    MemberAdded(MemberAdded),
+    /// A member was removed from a DAO space.
+    MemberRemoved(MemberRemoved),
+    /// An editor was added to a DAO space.
+    EditorAdded(EditorAdded),
+    /// A chain reorg invalidated blocks after `last_valid_block`.
+    UndoSignal(UndoSignal),
 }
 
 /// Event emitted when a new space is created.
@@ -142,6 +150,51 @@ pub struct EditPublished {
     pub ops: Vec<Op>,
 }
 
+/// Event emitted when a member is added to a DAO space.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberAdded {
+    /// Metadata about the block this event occurred in.
+    pub meta: BlockMetadata,
+    /// The DAO space gaining a member.
+    pub space_id: SpaceId,
+    /// The member's personal space.
+    pub member_space_id: SpaceId,
+}
+
+/// Event emitted when a member is removed from a DAO space.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberRemoved {
+    /// Metadata about the block this event occurred in.
+    pub meta: BlockMetadata,
+    /// The DAO space losing a member.
+    pub space_id: SpaceId,
+    /// The removed member's personal space.
+    pub member_space_id: SpaceId,
+}
+
+/// Event emitted when an editor is added to a DAO space.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditorAdded {
+    /// Metadata about the block this event occurred in.
+    pub meta: BlockMetadata,
+    /// The DAO space gaining an editor.
+    pub space_id: SpaceId,
+    /// The editor's personal space.
+    pub editor_space_id: SpaceId,
+}
+
+/// Signal that a chain reorg invalidated recent blocks.
+///
+/// Mirrors the substreams `BlockUndoSignal`. Consumers must roll back any
+/// state derived from blocks after `last_valid_block` and resume from `cursor`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UndoSignal {
+    /// The last block that is still part of the canonical chain.
+    pub last_valid_block: u64,
+    /// Cursor of the last valid block.
+    pub cursor: String,
+}
+
 /// A GRC-20 operation.
 ///
 /// These operations mirror the wire/grc20 protobuf definitions.
@@ -5,6 +5,9 @@
 
 use crate::events::*;
 
+/// Nominal time between blocks, in seconds.
+pub const BLOCK_TIME_SECS: u64 = 12;
+
 /// Configuration for the mock substream generator.
 #[derive(Debug, Clone)]
 pub struct MockConfig {
@@ -21,6 +24,27 @@ pub struct MockConfig {
     pub start_block: u64,
     /// Starting timestamp (unix seconds).
     pub start_timestamp: u64,
+    /// Minimum number of events per block in random mode.
+    pub min_events_per_block: usize,
+    /// Maximum number of events per block in random mode.
+    /// Block sizes are drawn uniformly from `min_events_per_block..=max_events_per_block`.
+    pub max_events_per_block: usize,
+    /// Multiplier on the probability of each random trust edge.
+    /// 1.0 keeps the defaults (30% verified, 20% related, 15% subtopic per space).
+    pub trust_density: f64,
+    /// Whether to shuffle events within each block in random mode.
+    /// Space creations are always kept ahead of the other events in their block,
+    /// so a block never references a space before creating it.
+    pub shuffle_within_block: bool,
+    /// Maximum deviation (in seconds) from the nominal block time in random mode.
+    /// Jitter alone never moves a block's timestamp at or before the previous block's.
+    pub block_time_jitter: u64,
+    /// Probability of skipping a block number before each block in random mode.
+    /// Skips repeat with the same probability, so gap lengths are geometric.
+    pub block_gap_probability: f64,
+    /// Probability that a block's timestamp is earlier than the previous block's
+    /// in random mode.
+    pub out_of_order_probability: f64,
 }
 
 impl Default for MockConfig {
@@ -32,6 +56,13 @@ impl Default for MockConfig {
             edits_per_space: 5,
             start_block: 1_000_000,
             start_timestamp: 1_700_000_000,
+            min_events_per_block: 1,
+            max_events_per_block: 1,
+            trust_density: 1.0,
+            shuffle_within_block: false,
+            block_time_jitter: 0,
+            block_gap_probability: 0.0,
+            out_of_order_probability: 0.0,
         }
     }
 }
@@ -62,6 +93,53 @@ impl MockConfig {
         self.edits_per_space = edits_per_space;
         self
     }
+
+    /// Set the range of events per block used in random mode.
+    ///
+    /// A minimum of zero is treated as one, and a maximum below the minimum
+    /// is raised to the minimum.
+    pub fn with_events_per_block(mut self, min: usize, max: usize) -> Self {
+        self.min_events_per_block = min.max(1);
+        self.max_events_per_block = max.max(self.min_events_per_block);
+        self
+    }
+
+    /// Set the trust edge density multiplier used in random mode.
+    ///
+    /// Negative values are treated as zero. Probabilities are capped at 1.
+    pub fn with_trust_density(mut self, trust_density: f64) -> Self {
+        self.trust_density = trust_density.max(0.0);
+        self
+    }
+
+    /// Shuffle events within each block in random mode.
+    pub fn with_shuffled_blocks(mut self) -> Self {
+        self.shuffle_within_block = true;
+        self
+    }
+
+    /// Vary block times by up to `max_jitter` seconds in random mode.
+    pub fn with_block_time_jitter(mut self, max_jitter: u64) -> Self {
+        self.block_time_jitter = max_jitter;
+        self
+    }
+
+    /// Skip block numbers with probability `p` before each block in random mode.
+    ///
+    /// `p` is clamped to `0.0..=0.9` so gaps stay finite.
+    pub fn with_block_gaps(mut self, p: f64) -> Self {
+        self.block_gap_probability = p.clamp(0.0, 0.9);
+        self
+    }
+
+    /// Give blocks a timestamp earlier than the previous block with probability `p`
+    /// in random mode.
+    ///
+    /// `p` is clamped to `0.0..=1.0`.
+    pub fn with_out_of_order_timestamps(mut self, p: f64) -> Self {
+        self.out_of_order_probability = p.clamp(0.0, 1.0);
+        self
+    }
 }
 
 /// A mock substream that generates blockchain events.
@@ -103,7 +181,7 @@ impl MockSubstream {
 
         // Advance state
         self.current_block += 1;
-        self.current_timestamp += 12; // ~12 second block time
+        self.current_timestamp += BLOCK_TIME_SECS;
 
         block
     }
@@ -118,7 +196,7 @@ impl MockSubstream {
         };
 
         self.current_block += 1;
-        self.current_timestamp += 12;
+        self.current_timestamp += BLOCK_TIME_SECS;
 
         block
     }
@@ -212,6 +290,62 @@ impl MockSubstream {
         }
     }
 
+    /// Create a DAO member added event.
+    pub fn add_member(&mut self, space_id: SpaceId, member_space_id: SpaceId) -> MemberAdded {
+        self.event_counter += 1;
+        MemberAdded {
+            meta: self.current_metadata(),
+            space_id,
+            member_space_id,
+        }
+    }
+
+    /// Create a DAO member removed event.
+    pub fn remove_member(&mut self, space_id: SpaceId, member_space_id: SpaceId) -> MemberRemoved {
+        self.event_counter += 1;
+        MemberRemoved {
+            meta: self.current_metadata(),
+            space_id,
+            member_space_id,
+        }
+    }
+
+    /// Create a DAO editor added event.
+    pub fn add_editor(&mut self, space_id: SpaceId, editor_space_id: SpaceId) -> EditorAdded {
+        self.event_counter += 1;
+        EditorAdded {
+            meta: self.current_metadata(),
+            space_id,
+            editor_space_id,
+        }
+    }
+
+    /// Roll back the last `n` blocks to simulate a chain reorg.
+    ///
+    /// Rewinds the generator so the next block reuses the first orphaned
+    /// block number, and returns the signal consumers would receive. `n` is
+    /// clamped to the number of blocks produced so far.
+    ///
+    /// Returns `None` when every generated block is orphaned: the generator
+    /// is rewound to the start block and there is no valid block (or cursor)
+    /// left to resume from, so consumers must restart from genesis.
+    pub fn undo_blocks(&mut self, n: u64) -> Option<UndoSignal> {
+        let n = n.min(self.current_block - self.config.start_block);
+
+        self.current_block -= n;
+        self.current_timestamp -= BLOCK_TIME_SECS * n;
+
+        if self.current_block == self.config.start_block {
+            return None;
+        }
+
+        let last_valid_block = self.current_block - 1;
+        Some(UndoSignal {
+            last_valid_block,
+            cursor: format!("cursor_{}", last_valid_block),
+        })
+    }
+
     /// Get the current block number.
     pub fn current_block_number(&self) -> u64 {
         self.current_block
@@ -231,6 +365,7 @@ impl MockSubstream {
 #[cfg(feature = "random")]
 mod random_impl {
     use super::*;
+    use rand::seq::SliceRandom;
     use rand::Rng;
 
     impl MockSubstream {
@@ -259,8 +394,12 @@ mod random_impl {
         }
 
         /// Generate random events based on the configuration.
+        ///
+        /// Events are generated in phase order (spaces, then trust edges, then
+        /// edits) and packed into blocks of `min_events_per_block..=max_events_per_block`
+        /// events each.
         pub fn generate_random_topology<R: Rng>(&mut self, rng: &mut R) -> Vec<MockBlock> {
-            let mut blocks = Vec::new();
+            let mut events = Vec::new();
             let mut spaces: Vec<(SpaceId, TopicId)> = Vec::new();
 
             // Generate spaces
@@ -282,37 +421,39 @@ mod random_impl {
                 };
 
                 let event = self.create_space(space_id, topic_id, space_type);
-                blocks.push(self.block_with_events(vec![MockEvent::SpaceCreated(event)]));
+                events.push(MockEvent::SpaceCreated(event));
                 spaces.push((space_id, topic_id));
             }
 
             // Generate trust edges
+            let density = self.config.trust_density.max(0.0);
+            let edge_chance = |p: f64| (p * density).min(1.0);
             for i in 0..spaces.len() {
                 let source = spaces[i].0;
 
                 // 30% chance of verified edge
-                if rng.gen_bool(0.3) && i + 1 < spaces.len() {
+                if rng.gen_bool(edge_chance(0.3)) && i + 1 < spaces.len() {
                     let target_idx = rng.gen_range(0..spaces.len());
                     if target_idx != i {
                         let event = self.extend_verified(source, spaces[target_idx].0);
-                        blocks.push(self.block_with_events(vec![MockEvent::TrustExtended(event)]));
+                        events.push(MockEvent::TrustExtended(event));
                     }
                 }
 
                 // 20% chance of related edge
-                if rng.gen_bool(0.2) {
+                if rng.gen_bool(edge_chance(0.2)) {
                     let target_idx = rng.gen_range(0..spaces.len());
                     if target_idx != i {
                         let event = self.extend_related(source, spaces[target_idx].0);
-                        blocks.push(self.block_with_events(vec![MockEvent::TrustExtended(event)]));
+                        events.push(MockEvent::TrustExtended(event));
                     }
                 }
 
                 // 15% chance of subtopic edge
-                if rng.gen_bool(0.15) {
+                if rng.gen_bool(edge_chance(0.15)) {
                     let target_idx = rng.gen_range(0..spaces.len());
                     let event = self.extend_subtopic(source, spaces[target_idx].1);
-                    blocks.push(self.block_with_events(vec![MockEvent::TrustExtended(event)]));
+                    events.push(MockEvent::TrustExtended(event));
                 }
             }
 
@@ -331,14 +472,92 @@ mod random_impl {
                             format!("Edit {}", j),
                             ops,
                         );
-                        blocks.push(self.block_with_events(vec![MockEvent::EditPublished(event)]));
+                        events.push(MockEvent::EditPublished(event));
                     }
                 }
             }
 
+            self.pack_into_blocks(events, rng)
+        }
+
+        /// Pack events into blocks of random size, in order.
+        ///
+        /// Each event's metadata is rewritten to match the block it lands in.
+        /// When `shuffle_within_block` is set, events are shuffled within their
+        /// block with space creations kept first. Block gaps, block time jitter
+        /// and out-of-order timestamps are applied between blocks as configured.
+        fn pack_into_blocks<R: Rng>(
+            &mut self,
+            events: Vec<MockEvent>,
+            rng: &mut R,
+        ) -> Vec<MockBlock> {
+            let min = self.config.min_events_per_block.max(1);
+            let max = self.config.max_events_per_block.max(min);
+
+            let mut blocks: Vec<MockBlock> = Vec::new();
+            let mut remaining = events.into_iter().peekable();
+
+            while remaining.peek().is_some() {
+                // Perturbations only affect this block, later blocks keep the nominal schedule
+                let nominal_timestamp = match blocks.last() {
+                    Some(previous) => self.perturb_next_block(previous.timestamp, rng),
+                    None => self.current_timestamp,
+                };
+
+                let size = rng.gen_range(min..=max);
+                let mut block_events: Vec<MockEvent> = remaining.by_ref().take(size).collect();
+
+                if self.config.shuffle_within_block {
+                    block_events.shuffle(rng);
+                    // Stable sort keeps the shuffled order within each group
+                    block_events.sort_by_key(|event| !matches!(event, MockEvent::SpaceCreated(_)));
+                }
+
+                for event in &mut block_events {
+                    if let Some(meta) = event_meta_mut(event) {
+                        meta.block_number = self.current_block;
+                        meta.block_timestamp = self.current_timestamp;
+                        meta.cursor = format!("cursor_{}", self.current_block);
+                    }
+                }
+
+                blocks.push(self.block_with_events(block_events));
+                self.current_timestamp = nominal_timestamp + BLOCK_TIME_SECS;
+            }
+
             blocks
         }
 
+        /// Apply block gaps, block time jitter and out-of-order timestamps
+        /// before the next block.
+        ///
+        /// Sets the next block's number and timestamp, and returns the
+        /// timestamp the block would have had without jitter.
+        fn perturb_next_block<R: Rng>(&mut self, previous_timestamp: u64, rng: &mut R) -> u64 {
+            let gap_probability = self.config.block_gap_probability.clamp(0.0, 0.9);
+            while gap_probability > 0.0 && rng.gen_bool(gap_probability) {
+                self.current_block += 1;
+                self.current_timestamp += BLOCK_TIME_SECS;
+            }
+            let nominal_timestamp = self.current_timestamp;
+
+            let jitter = self.config.block_time_jitter;
+            if jitter > 0 {
+                let offset = rng.gen_range(0..=2 * jitter);
+                self.current_timestamp = (nominal_timestamp + offset)
+                    .saturating_sub(jitter)
+                    .max(previous_timestamp + 1);
+            }
+
+            let out_of_order = self.config.out_of_order_probability.clamp(0.0, 1.0);
+            if out_of_order > 0.0 && rng.gen_bool(out_of_order) {
+                self.current_timestamp =
+                    previous_timestamp.saturating_sub(rng.gen_range(1..=BLOCK_TIME_SECS));
+            }
+
+            nominal_timestamp
+        }
+
         /// Generate a random 16-byte ID.
         fn random_id<R: Rng>(rng: &mut R) -> [u8; 16] {
             let mut id = [0u8; 16];
@@ -399,6 +618,19 @@ mod random_impl {
             ops
         }
     }
+
+    /// Get mutable access to an event's block metadata.
+    fn event_meta_mut(event: &mut MockEvent) -> Option<&mut BlockMetadata> {
+        match event {
+            MockEvent::SpaceCreated(e) => Some(&mut e.meta),
+            MockEvent::TrustExtended(e) => Some(&mut e.meta),
+            MockEvent::EditPublished(e) => Some(&mut e.meta),
+            MockEvent::MemberAdded(e) => Some(&mut e.meta),
+            MockEvent::MemberRemoved(e) => Some(&mut e.meta),
+            MockEvent::EditorAdded(e) => Some(&mut e.meta),
+            MockEvent::UndoSignal(_) => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -422,6 +654,63 @@ mod tests {
         assert_eq!(block2.timestamp - block1.timestamp, 12);
     }
 
+    #[test]
+    fn test_dao_governance_events() {
+        let mut mock = MockSubstream::deterministic();
+        let dao = make_id(0x01);
+        let member = make_id(0x02);
+
+        let added = mock.add_member(dao, member);
+        assert_eq!(added.space_id, dao);
+        assert_eq!(added.member_space_id, member);
+
+        let removed = mock.remove_member(dao, member);
+        assert_eq!(removed.member_space_id, member);
+        assert_ne!(added.meta.tx_hash, removed.meta.tx_hash);
+
+        let editor = mock.add_editor(dao, member);
+        assert_eq!(editor.editor_space_id, member);
+    }
+
+    #[test]
+    fn test_undo_blocks_rewinds_state() {
+        let mut mock = MockSubstream::deterministic();
+        let block0 = mock.next_block();
+        let block1 = mock.next_block();
+        mock.next_block();
+
+        let undo = mock.undo_blocks(2).expect("block 0 is still valid");
+        assert_eq!(undo.last_valid_block, block0.number);
+        assert_eq!(undo.cursor, block0.cursor);
+
+        // The replacement block reuses the orphaned number and timestamp
+        let replacement = mock.next_block();
+        assert_eq!(replacement.number, block1.number);
+        assert_eq!(replacement.timestamp, block1.timestamp);
+    }
+
+    #[test]
+    fn test_undo_blocks_clamped_to_start() {
+        let mut mock = MockSubstream::deterministic();
+        let block0 = mock.next_block();
+
+        // Every generated block is orphaned, so there is no valid block left
+        assert_eq!(mock.undo_blocks(10), None);
+        assert_eq!(mock.current_block_number(), 1_000_000);
+
+        // Generation restarts from the start block
+        let replacement = mock.next_block();
+        assert_eq!(replacement.number, block0.number);
+        assert_eq!(replacement.timestamp, block0.timestamp);
+    }
+
+    #[test]
+    fn test_undo_blocks_without_blocks() {
+        let mut mock = MockSubstream::deterministic();
+        assert_eq!(mock.undo_blocks(1), None);
+        assert_eq!(mock.current_block_number(), 1_000_000);
+    }
+
     #[test]
     fn test_create_personal_space() {
         let mut mock = MockSubstream::deterministic();
@@ -464,4 +753,145 @@ mod tests {
             _ => panic!("Expected subtopic extension"),
         }
     }
+
+    #[test]
+    fn test_events_per_block_clamped() {
+        let config = MockConfig::default().with_events_per_block(0, 0);
+        assert_eq!(config.min_events_per_block, 1);
+        assert_eq!(config.max_events_per_block, 1);
+
+        let config = MockConfig::default().with_events_per_block(5, 2);
+        assert_eq!(config.min_events_per_block, 5);
+        assert_eq!(config.max_events_per_block, 5);
+    }
+
+    #[cfg(feature = "random")]
+    fn event_block_number(event: &MockEvent) -> u64 {
+        match event {
+            MockEvent::SpaceCreated(e) => e.meta.block_number,
+            MockEvent::TrustExtended(e) => e.meta.block_number,
+            MockEvent::EditPublished(e) => e.meta.block_number,
+            other => panic!("Unexpected event in random topology: {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "random")]
+    #[test]
+    fn test_random_topology_multi_event_blocks() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let config = MockConfig::default()
+            .with_num_spaces(50)
+            .with_edits()
+            .with_edits_per_space(2)
+            .with_events_per_block(2, 6);
+        let mut mock = MockSubstream::new(config);
+        let blocks = mock.generate_random_topology(&mut StdRng::seed_from_u64(7));
+
+        let total: usize = blocks.iter().map(|b| b.events.len()).sum();
+        assert!(total >= 150);
+
+        let (last, full) = blocks.split_last().unwrap();
+        assert!(full.iter().all(|b| (2..=6).contains(&b.events.len())));
+        assert!((1..=6).contains(&last.events.len()));
+
+        for (i, block) in blocks.iter().enumerate() {
+            assert_eq!(block.number, 1_000_000 + i as u64);
+            assert!(block
+                .events
+                .iter()
+                .all(|e| event_block_number(e) == block.number));
+        }
+    }
+
+    #[cfg(feature = "random")]
+    #[test]
+    fn test_random_topology_trust_density() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let trust_count = |density: f64| {
+            let config = MockConfig::default()
+                .with_num_spaces(40)
+                .with_trust_density(density);
+            MockSubstream::new(config)
+                .generate_random_topology(&mut StdRng::seed_from_u64(3))
+                .iter()
+                .flat_map(|b| &b.events)
+                .filter(|e| matches!(e, MockEvent::TrustExtended(_)))
+                .count()
+        };
+
+        assert_eq!(trust_count(0.0), 0);
+        assert!(trust_count(10.0) > trust_count(1.0));
+    }
+
+    #[cfg(feature = "random")]
+    #[test]
+    fn test_random_topology_irregular_blocks() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let generate = |config: MockConfig| {
+            MockSubstream::new(config.with_num_spaces(200))
+                .generate_random_topology(&mut StdRng::seed_from_u64(5))
+        };
+
+        // Gaps skip block numbers but never reorder them
+        let blocks = generate(MockConfig::default().with_block_gaps(0.5));
+        assert!(blocks.windows(2).all(|w| w[1].number > w[0].number));
+        assert!(blocks.windows(2).any(|w| w[1].number > w[0].number + 1));
+        for block in &blocks {
+            assert!(block
+                .events
+                .iter()
+                .all(|e| event_block_number(e) == block.number));
+        }
+
+        // Jitter varies block times but keeps timestamps increasing
+        let blocks = generate(MockConfig::default().with_block_time_jitter(5));
+        let intervals: Vec<u64> = blocks
+            .windows(2)
+            .map(|w| w[1].timestamp - w[0].timestamp)
+            .collect();
+        assert!(intervals.iter().any(|&i| i != BLOCK_TIME_SECS));
+        assert!(intervals
+            .iter()
+            .all(|&i| i > 0 && i <= BLOCK_TIME_SECS + 10));
+
+        // Out-of-order timestamps go backwards without drifting the schedule
+        let blocks = generate(MockConfig::default().with_out_of_order_timestamps(0.2));
+        assert!(blocks.windows(2).any(|w| w[1].timestamp < w[0].timestamp));
+        let last = blocks.last().unwrap();
+        assert!(
+            last.timestamp + BLOCK_TIME_SECS
+                >= 1_700_000_000 + BLOCK_TIME_SECS * (blocks.len() as u64 - 1)
+        );
+    }
+
+    #[cfg(feature = "random")]
+    #[test]
+    fn test_random_topology_shuffled_blocks_create_spaces_first() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let config = MockConfig::default()
+            .with_num_spaces(30)
+            .with_events_per_block(4, 12)
+            .with_shuffled_blocks();
+        let mut mock = MockSubstream::new(config);
+        let blocks = mock.generate_random_topology(&mut StdRng::seed_from_u64(11));
+
+        for block in &blocks {
+            let first_other = block
+                .events
+                .iter()
+                .position(|e| !matches!(e, MockEvent::SpaceCreated(_)))
+                .unwrap_or(block.events.len());
+            assert!(block.events[first_other..]
+                .iter()
+                .all(|e| !matches!(e, MockEvent::SpaceCreated(_))));
+        }
+    }
 }
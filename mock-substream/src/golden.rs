@@ -0,0 +1,400 @@
+//! Golden snapshots of the deterministic topology's expected outputs.
+//!
+//! Renders the downstream artifacts a correct pipeline should produce from
+//! [`test_topology::generate`] as JSON:
+//!
+//! - `canonical_set.json`: the root space and the sorted canonical space IDs.
+//! - `entities.json`: the entity documents obtained by folding every edit's
+//!   operations in block order.
+//!
+//! The rendered files are committed under `mock-substream/golden/`. Pipeline
+//! tests compare their own outputs against them with [`assert_canonical_set`]
+//! and [`assert_entities`], so a refactor that changes behavior shows up as a
+//! golden diff.
+//!
+//! To regenerate the committed files after an intentional topology change, run
+//! the tests with `UPDATE_GOLDEN=1`.
+//!
+//! IDs are rendered as lowercase hex without a `0x` prefix.
+
+use crate::events::*;
+use crate::test_topology;
+use serde_json::{json, Map, Value as Json};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+/// File name of the canonical set golden file.
+pub const CANONICAL_SET_FILE: &str = "canonical_set.json";
+
+/// File name of the entity documents golden file.
+pub const ENTITIES_FILE: &str = "entities.json";
+
+/// Environment variable that makes assertions rewrite golden files instead of comparing.
+pub const UPDATE_ENV: &str = "UPDATE_GOLDEN";
+
+/// An entity document expected downstream of the edits in a topology.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectedEntity {
+    /// The space the entity's values were written in.
+    pub space_id: SpaceId,
+    /// The entity ID.
+    pub entity_id: EntityId,
+    /// Current property values, keyed by property ID.
+    pub values: BTreeMap<PropertyId, String>,
+    /// Outgoing relations, sorted by relation ID.
+    pub relations: Vec<ExpectedRelation>,
+}
+
+/// An outgoing relation on an expected entity document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectedRelation {
+    /// The relation ID.
+    pub id: RelationId,
+    /// The relation type ID.
+    pub relation_type: RelationTypeId,
+    /// The target entity ID.
+    pub to_entity: EntityId,
+    /// The target space ID, if set.
+    pub to_space: Option<SpaceId>,
+}
+
+/// Directory holding the committed golden files.
+pub fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("golden")
+}
+
+/// Fold the edits in `blocks` into entity documents.
+///
+/// Documents are keyed by (space, entity) and returned in that order.
+/// Relations are attached to their source entity in `from_space`, falling back
+/// to the space the edit was published in.
+pub fn expected_entities(blocks: &[MockBlock]) -> Vec<ExpectedEntity> {
+    let mut docs: BTreeMap<(SpaceId, EntityId), ExpectedEntity> = BTreeMap::new();
+    // Relation ID -> owning document, for updates and deletes
+    let mut relation_owner: BTreeMap<RelationId, (SpaceId, EntityId)> = BTreeMap::new();
+
+    let edits = blocks
+        .iter()
+        .flat_map(|b| &b.events)
+        .filter_map(|event| match event {
+            MockEvent::EditPublished(edit) => Some(edit),
+            _ => None,
+        });
+
+    for edit in edits {
+        for op in &edit.ops {
+            match op {
+                Op::UpdateEntity(update) => {
+                    let doc = document(&mut docs, edit.space_id, update.id);
+                    for value in &update.values {
+                        doc.values.insert(value.property, value.value.clone());
+                    }
+                }
+                Op::UnsetEntityValues(unset) => {
+                    if let Some(doc) = docs.get_mut(&(edit.space_id, unset.id)) {
+                        for property in &unset.properties {
+                            doc.values.remove(property);
+                        }
+                    }
+                }
+                Op::CreateRelation(relation) => {
+                    let key = (
+                        relation.from_space.unwrap_or(edit.space_id),
+                        relation.from_entity,
+                    );
+                    let doc = document(&mut docs, key.0, key.1);
+                    doc.relations.retain(|r| r.id != relation.id);
+                    doc.relations.push(ExpectedRelation {
+                        id: relation.id,
+                        relation_type: relation.relation_type,
+                        to_entity: relation.to_entity,
+                        to_space: relation.to_space,
+                    });
+                    doc.relations.sort_by_key(|r| r.id);
+                    relation_owner.insert(relation.id, key);
+                }
+                Op::UpdateRelation(update) => {
+                    if let Some(relation) = find_relation(&mut docs, &relation_owner, update.id) {
+                        if update.to_space.is_some() {
+                            relation.to_space = update.to_space;
+                        }
+                    }
+                }
+                Op::UnsetRelationFields(unset) => {
+                    if unset.to_space == Some(true) {
+                        if let Some(relation) = find_relation(&mut docs, &relation_owner, unset.id)
+                        {
+                            relation.to_space = None;
+                        }
+                    }
+                }
+                Op::DeleteRelation(id) => {
+                    if let Some(key) = relation_owner.remove(id) {
+                        if let Some(doc) = docs.get_mut(&key) {
+                            doc.relations.retain(|r| r.id != *id);
+                        }
+                    }
+                }
+                Op::CreateProperty(_) => {}
+            }
+        }
+    }
+
+    docs.into_values().collect()
+}
+
+/// Render the canonical set golden JSON for a root and its canonical spaces.
+///
+/// Spaces are sorted, so callers may pass them in any order.
+pub fn canonical_set_json<'a>(
+    root: &SpaceId,
+    spaces: impl IntoIterator<Item = &'a SpaceId>,
+) -> Json {
+    let mut spaces: Vec<&SpaceId> = spaces.into_iter().collect();
+    spaces.sort();
+    spaces.dedup();
+
+    json!({
+        "root": hex(root),
+        "spaces": spaces.into_iter().map(|id| hex(id)).collect::<Vec<_>>(),
+    })
+}
+
+/// Render the entity documents golden JSON.
+pub fn entities_json(entities: &[ExpectedEntity]) -> Json {
+    Json::Array(
+        entities
+            .iter()
+            .map(|entity| {
+                let values: Map<String, Json> = entity
+                    .values
+                    .iter()
+                    .map(|(property, value)| (hex(property), Json::String(value.clone())))
+                    .collect();
+                let relations: Vec<Json> = entity
+                    .relations
+                    .iter()
+                    .map(|relation| {
+                        json!({
+                            "id": hex(&relation.id),
+                            "relation_type": hex(&relation.relation_type),
+                            "to_entity": hex(&relation.to_entity),
+                            "to_space": relation.to_space.as_ref().map(|id| hex(id)),
+                        })
+                    })
+                    .collect();
+
+                json!({
+                    "space_id": hex(&entity.space_id),
+                    "entity_id": hex(&entity.entity_id),
+                    "values": values,
+                    "relations": relations,
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Render every golden file for the deterministic topology, as (file name, JSON) pairs.
+pub fn render() -> Vec<(&'static str, Json)> {
+    let blocks = test_topology::generate();
+    vec![
+        (
+            CANONICAL_SET_FILE,
+            canonical_set_json(
+                &test_topology::ROOT_SPACE_ID,
+                &test_topology::canonical_spaces(),
+            ),
+        ),
+        (ENTITIES_FILE, entities_json(&expected_entities(&blocks))),
+    ]
+}
+
+/// Write every golden file for the deterministic topology into `dir`.
+pub fn write_all(dir: &Path) -> io::Result<()> {
+    for (name, json) in render() {
+        write_file(&dir.join(name), &json)?;
+    }
+    Ok(())
+}
+
+/// Assert that a pipeline's canonical set matches the committed golden file.
+///
+/// # Panics
+///
+/// Panics with both renderings if the sets differ.
+pub fn assert_canonical_set<'a>(root: &SpaceId, spaces: impl IntoIterator<Item = &'a SpaceId>) {
+    assert_golden(CANONICAL_SET_FILE, &canonical_set_json(root, spaces));
+}
+
+/// Assert that a pipeline's entity documents match the committed golden file.
+///
+/// # Panics
+///
+/// Panics with both renderings if the documents differ.
+pub fn assert_entities(entities: &[ExpectedEntity]) {
+    assert_golden(ENTITIES_FILE, &entities_json(entities));
+}
+
+/// Assert that `actual` matches the committed golden file `name`.
+///
+/// When `UPDATE_GOLDEN` is set, the golden file is rewritten with `actual`
+/// instead.
+///
+/// # Panics
+///
+/// Panics if the golden file is missing or unreadable, or if it differs from `actual`.
+pub fn assert_golden(name: &str, actual: &Json) {
+    let path = golden_dir().join(name);
+
+    if std::env::var_os(UPDATE_ENV).is_some() {
+        write_file(&path, actual)
+            .unwrap_or_else(|e| panic!("failed to write {}: {}", path.display(), e));
+        return;
+    }
+
+    let contents = fs::read_to_string(&path).unwrap_or_else(|e| {
+        panic!(
+            "failed to read golden file {}: {} (run with {}=1 to create it)",
+            path.display(),
+            e,
+            UPDATE_ENV
+        )
+    });
+    let expected: Json = serde_json::from_str(&contents)
+        .unwrap_or_else(|e| panic!("invalid golden file {}: {}", path.display(), e));
+
+    if &expected != actual {
+        panic!(
+            "output differs from golden file {}\n--- expected\n{}\n+++ actual\n{}\n(run with {}=1 to accept the new output)",
+            path.display(),
+            pretty(&expected),
+            pretty(actual),
+            UPDATE_ENV
+        );
+    }
+}
+
+/// Get or create the document for an entity in a space.
+fn document(
+    docs: &mut BTreeMap<(SpaceId, EntityId), ExpectedEntity>,
+    space_id: SpaceId,
+    entity_id: EntityId,
+) -> &mut ExpectedEntity {
+    docs.entry((space_id, entity_id))
+        .or_insert_with(|| ExpectedEntity {
+            space_id,
+            entity_id,
+            values: BTreeMap::new(),
+            relations: Vec::new(),
+        })
+}
+
+/// Find a previously created relation by ID.
+fn find_relation<'a>(
+    docs: &'a mut BTreeMap<(SpaceId, EntityId), ExpectedEntity>,
+    relation_owner: &BTreeMap<RelationId, (SpaceId, EntityId)>,
+    id: RelationId,
+) -> Option<&'a mut ExpectedRelation> {
+    let key = relation_owner.get(&id)?;
+    docs.get_mut(key)?.relations.iter_mut().find(|r| r.id == id)
+}
+
+fn write_file(path: &Path, json: &Json) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, pretty(json) + "\n")
+}
+
+fn pretty(json: &Json) -> String {
+    serde_json::to_string_pretty(json).expect("JSON values always serialize")
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_topology::*;
+
+    #[test]
+    fn test_golden_files_up_to_date() {
+        for (name, json) in render() {
+            assert_golden(name, &json);
+        }
+    }
+
+    #[test]
+    fn test_expected_entities_fold_edits() {
+        let entities = expected_entities(&generate());
+        assert_eq!(entities.len(), 6);
+
+        let person = entities
+            .iter()
+            .find(|e| e.space_id == ROOT_SPACE_ID && e.entity_id == ENTITY_PERSON_1)
+            .unwrap();
+        assert_eq!(person.values.len(), 2);
+        assert_eq!(person.values[&PROPERTY_DESCRIPTION], "A software engineer");
+
+        let topic = entities
+            .iter()
+            .find(|e| e.entity_id == ENTITY_TOPIC_1)
+            .unwrap();
+        assert_eq!(topic.space_id, SPACE_C);
+        assert_eq!(topic.relations.len(), 1);
+        assert_eq!(topic.relations[0].to_space, Some(SPACE_B));
+    }
+
+    #[test]
+    fn test_expected_entities_delete_relation() {
+        let mut mock = crate::MockSubstream::deterministic();
+        let relation = CreateRelation {
+            id: RELATION_1,
+            relation_type: RELATION_TYPE_RELATED_TO,
+            from_entity: ENTITY_ORG_1,
+            from_space: None,
+            to_entity: ENTITY_DOC_1,
+            to_space: None,
+            entity: make_id(0xB4),
+            position: None,
+            verified: None,
+        };
+        let create = mock.publish_edit(
+            EDIT_A_1,
+            SPACE_A,
+            vec![USER_1],
+            "create".to_string(),
+            vec![Op::CreateRelation(relation)],
+        );
+        let delete = mock.publish_edit(
+            EDIT_A_2,
+            SPACE_A,
+            vec![USER_1],
+            "delete".to_string(),
+            vec![Op::DeleteRelation(RELATION_1)],
+        );
+        let blocks = vec![mock.block_with_events(vec![
+            MockEvent::EditPublished(create),
+            MockEvent::EditPublished(delete),
+        ])];
+
+        let entities = expected_entities(&blocks);
+        assert_eq!(entities.len(), 1);
+        assert!(entities[0].relations.is_empty());
+    }
+
+    #[test]
+    fn test_canonical_set_json_is_order_independent() {
+        let mut reversed = canonical_spaces();
+        reversed.reverse();
+
+        assert_eq!(
+            canonical_set_json(&ROOT_SPACE_ID, &reversed),
+            canonical_set_json(&ROOT_SPACE_ID, &canonical_spaces())
+        );
+    }
+}
@@ -63,6 +63,32 @@
 //! ]);
 //! ```
 //!
+//! ## Chain Reorgs
+//!
+//! Rewind the generator to simulate a reorg. Blocks produced afterwards reuse
+//! the orphaned block numbers, as they would on a real fork:
+//!
+//! ```rust
+//! use mock_substream::{MockSubstream, MockEvent};
+//!
+//! let mut mock = MockSubstream::deterministic();
+//! let _b0 = mock.next_block();
+//! let _b1 = mock.next_block();
+//! let _b2 = mock.next_block();
+//!
+//! // Blocks 1 and 2 are orphaned
+//! let undo = mock.undo_blocks(2).expect("block 0 is still valid");
+//! assert_eq!(undo.last_valid_block, 1_000_000);
+//!
+//! let signal = MockEvent::UndoSignal(undo);
+//! let replacement = mock.next_block();
+//! assert_eq!(replacement.number, 1_000_001);
+//! # let _ = signal;
+//!
+//! // Orphaning every block leaves nothing to resume from
+//! assert_eq!(mock.undo_blocks(10), None);
+//! ```
+//!
 //! ## Random Generation (requires `random` feature)
 //!
 //! ```rust,ignore
@@ -72,18 +98,36 @@
 //! let config = MockConfig::default()
 //!     .with_num_spaces(20)
 //!     .with_edits()
-//!     .with_edits_per_space(10);
+//!     .with_edits_per_space(10)
+//!     // Bursty blocks of 1-8 events, shuffled within each block
+//!     .with_events_per_block(1, 8)
+//!     .with_shuffled_blocks()
+//!     // Non-ideal chain: ±4s block times, skipped blocks, occasional clock skew
+//!     .with_block_time_jitter(4)
+//!     .with_block_gaps(0.1)
+//!     .with_out_of_order_timestamps(0.05);
 //!
 //! let mut mock = MockSubstream::new(config);
 //! let blocks = mock.generate_random_topology(&mut thread_rng());
 //! ```
 //!
+//! Within a block, space creations always precede the events that may
+//! reference them.
+//!
+//! Block numbers always increase, even with gaps. Jitter keeps timestamps
+//! increasing; only `with_out_of_order_timestamps` moves a block before its
+//! predecessor.
+//!
 //! # Features
 //!
 //! - `random`: Enables random event generation using the `rand` crate.
+//! - `golden`: Enables the [`golden`] module, which renders the deterministic
+//!   topology's expected outputs as JSON golden files for regression tests.
 
 pub mod events;
 pub mod generator;
+#[cfg(feature = "golden")]
+pub mod golden;
 pub mod test_topology;
 
 // Re-export main types at crate root for convenience
@@ -98,6 +142,10 @@ pub use events::{
     TrustExtended, TrustExtension,
     // Edit events
     EditPublished, Op,
+    // DAO governance events
+    EditorAdded, MemberAdded, MemberRemoved,
+    // Chain reorgs
+    UndoSignal,
     // Op types
     CreateProperty, CreateRelation, DataType, UnsetEntityValues, UnsetRelationFields,
     UpdateEntity, UpdateRelation, Value,
@@ -105,4 +153,4 @@ pub use events::{
     make_address, make_id,
 };
 
-pub use generator::{MockConfig, MockSubstream};
+pub use generator::{MockConfig, MockSubstream, BLOCK_TIME_SECS};
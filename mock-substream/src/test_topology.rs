@@ -10,6 +10,7 @@
 //! - **11 canonical spaces** reachable from the Root space
 //! - **7 non-canonical spaces** in isolated islands
 //! - **Topic edges** demonstrating resolution behavior
+//! - **DAO governance** changes to the DAO space P
 //!
 //! ```text
 //! Canonical Graph:
@@ -32,6 +33,12 @@
 //!            └─topic[T_Q]─▶ Q
 //!
 //! Island 3: S (0x40)  [isolated]
+//!
+//! DAO Governance (P):
+//!
+//! created with editors [Q], members []
+//!   +member X, +member Y, +editor X, -member Y
+//! final:       editors [Q, X], members [X]
 //! ```
 
 use crate::events::*;
@@ -242,6 +249,7 @@ pub const RELATION_2: RelationId = make_id(0xB2);
 /// - 14 explicit trust edges
 /// - 5 topic-based trust edges
 /// - 6 edits with various GRC-20 operations
+/// - 4 DAO governance events on space P
 pub fn generate() -> Vec<MockBlock> {
     let mut mock = MockSubstream::deterministic();
     let mut blocks = Vec::new();
@@ -552,6 +560,26 @@ pub fn generate() -> Vec<MockBlock> {
     );
     blocks.push(mock.block_with_events(vec![MockEvent::EditPublished(edit_c_1)]));
 
+    // =========================================================================
+    // Phase 6: DAO governance on P
+    // =========================================================================
+
+    // X and Y join P
+    let p_add_x = mock.add_member(SPACE_P, SPACE_X);
+    let p_add_y = mock.add_member(SPACE_P, SPACE_Y);
+    blocks.push(mock.block_with_events(vec![
+        MockEvent::MemberAdded(p_add_x),
+        MockEvent::MemberAdded(p_add_y),
+    ]));
+
+    // X is promoted to editor
+    let p_editor_x = mock.add_editor(SPACE_P, SPACE_X);
+    blocks.push(mock.block_with_events(vec![MockEvent::EditorAdded(p_editor_x)]));
+
+    // Y leaves P
+    let p_remove_y = mock.remove_member(SPACE_P, SPACE_Y);
+    blocks.push(mock.block_with_events(vec![MockEvent::MemberRemoved(p_remove_y)]));
+
     blocks
 }
 
@@ -585,6 +613,16 @@ pub fn non_canonical_spaces() -> Vec<SpaceId> {
     ]
 }
 
+/// Get the editors of DAO space P after all governance events.
+pub fn dao_p_editors() -> Vec<SpaceId> {
+    vec![SPACE_Q, SPACE_X]
+}
+
+/// Get the members of DAO space P after all governance events.
+pub fn dao_p_members() -> Vec<SpaceId> {
+    vec![SPACE_X]
+}
+
 /// Get all space IDs in the topology.
 pub fn all_spaces() -> Vec<SpaceId> {
     let mut spaces = canonical_spaces();
@@ -607,6 +645,7 @@ mod tests {
         let mut space_count = 0;
         let mut trust_count = 0;
         let mut edit_count = 0;
+        let mut governance_count = 0;
 
         for block in &blocks {
             for event in &block.events {
@@ -614,6 +653,10 @@ mod tests {
                     MockEvent::SpaceCreated(_) => space_count += 1,
                     MockEvent::TrustExtended(_) => trust_count += 1,
                     MockEvent::EditPublished(_) => edit_count += 1,
+                    MockEvent::MemberAdded(_)
+                    | MockEvent::MemberRemoved(_)
+                    | MockEvent::EditorAdded(_) => governance_count += 1,
+                    MockEvent::UndoSignal(_) => panic!("Test topology has no reorgs"),
                 }
             }
         }
@@ -626,6 +669,48 @@ mod tests {
 
         // 6 edits: 2 in Root, 2 in A, 1 in B, 1 in C
         assert_eq!(edit_count, 6);
+
+        // 2 members added, 1 editor added, 1 member removed
+        assert_eq!(governance_count, 4);
+    }
+
+    #[test]
+    fn test_dao_governance_final_roles() {
+        let blocks = generate();
+
+        let (mut editors, mut members) = match blocks
+            .iter()
+            .flat_map(|b| &b.events)
+            .find_map(|e| match e {
+                MockEvent::SpaceCreated(created) if created.space_id == SPACE_P => {
+                    Some(created.space_type.clone())
+                }
+                _ => None,
+            }) {
+            Some(SpaceType::Dao {
+                initial_editors,
+                initial_members,
+            }) => (initial_editors, initial_members),
+            _ => panic!("Expected P to be a DAO space"),
+        };
+
+        for event in blocks.iter().flat_map(|b| &b.events) {
+            match event {
+                MockEvent::MemberAdded(e) if e.space_id == SPACE_P => {
+                    members.push(e.member_space_id)
+                }
+                MockEvent::MemberRemoved(e) if e.space_id == SPACE_P => {
+                    members.retain(|m| *m != e.member_space_id)
+                }
+                MockEvent::EditorAdded(e) if e.space_id == SPACE_P => {
+                    editors.push(e.editor_space_id)
+                }
+                _ => {}
+            }
+        }
+
+        assert_eq!(editors, dao_p_editors());
+        assert_eq!(members, dao_p_members());
     }
 
     #[test]